@@ -0,0 +1,111 @@
+#[macro_use]
+extern crate criterion;
+extern crate maxrects;
+
+use criterion::{Criterion, BatchSize};
+use maxrects::{RectPacker, Rect};
+
+/// A small xorshift generator, so that workloads are the same on every run.
+struct Rng(u32);
+
+impl Rng {
+    fn next(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0
+    }
+
+    /// A uniformly distributed number in `min..max`
+    fn range(&mut self, min: u32, max: u32) -> u32 {
+        min + self.next() % (max - min)
+    }
+}
+
+/// Sizes distributed uniformly between 8 and 64 on each side
+fn uniform(count: usize) -> Vec<(u32,u32)> {
+    let mut rng = Rng(0x9e3779b9);
+    (0..count).map(|_| (rng.range(8, 64), rng.range(8, 64))).collect()
+}
+
+/// Sprite-like sizes where small sprites are common and large ones rare
+fn power_law(count: usize) -> Vec<(u32,u32)> {
+    let mut rng = Rng(0x85ebca6b);
+    (0..count).map(|_| {
+        let scale = 1 << rng.range(0, 4);
+        (rng.range(4, 16) * scale, rng.range(4, 16) * scale)
+    }).collect()
+}
+
+/// Glyph-like sizes with a fixed line height and varying advance
+fn glyphs(count: usize) -> Vec<(u32,u32)> {
+    let mut rng = Rng(0xc2b2ae35);
+    (0..count).map(|_| (rng.range(2, 12), rng.range(10, 14))).collect()
+}
+
+fn packer(size: u32) -> RectPacker<u32> {
+    let mut packer = RectPacker::new();
+    packer.add_free((0,0), (size,size));
+    packer
+}
+
+fn workloads() -> Vec<(&'static str, Vec<(u32,u32)>)> {
+    vec![("uniform", uniform(500)), ("power_law", power_law(500)), ("glyphs", glyphs(2000))]
+}
+
+/// Packing one rectangle at a time is dominated by splitting the free rectangles
+fn bench_pack(c: &mut Criterion) {
+    for (name, sizes) in workloads() {
+        c.bench_function(&format!("pack/{}", name), |b| b.iter_batched(
+            || packer(2048),
+            |mut packer| for &(width, height) in sizes.iter() {
+                packer.pack(width, height);
+            },
+            BatchSize::SmallInput));
+    }
+}
+
+fn bench_pack_global(c: &mut Criterion) {
+    for (name, sizes) in workloads() {
+        let sizes: Vec<_> = sizes.into_iter().take(200).collect();
+        c.bench_function(&format!("pack_global/{}", name), |b| b.iter_batched(
+            || (packer(2048), sizes.clone()),
+            |(mut packer, sizes)| packer.pack_global(sizes, |&size| size).ok(),
+            BatchSize::SmallInput));
+    }
+}
+
+/// A packer whose free rectangles are `count` disjoint 4x4 cells, as left by packing many
+/// rectangles with gaps between them
+fn fragmented(count: u32) -> RectPacker<u32> {
+    let side = (1..).find(|x| x * x >= count).unwrap();
+    let mut packer = RectPacker::new();
+    for index in 0..count {
+        let (x, y) = (index % side * 5, index / side * 5);
+        packer.add_free((x,y), (x + 4,y + 4));
+    }
+    packer
+}
+
+/// Splitting the free rectangles on its own, for free lists of growing size
+fn bench_subtract_rect(c: &mut Criterion) {
+    let mut rng = Rng(0x27d4eb2f);
+    for &count in [1000, 10000, 40000].iter() {
+        let side = (1..).find(|x| x * x >= count).unwrap() * 5;
+        let rects: Vec<_> = (0..100).map(|_| {
+            let (x, y) = (rng.range(0, side - 4), rng.range(0, side - 4));
+            Rect::new((x,y), (x + rng.range(1, 4),y + rng.range(1, 4)))
+        }).collect();
+        let packer = fragmented(count);
+
+        c.bench_function(&format!("subtract_rect/{}", count), |b| b.iter_batched(
+            || packer.clone(),
+            |mut packer| for rect in rects.iter() {
+                packer.subtract_free(rect);
+            },
+            BatchSize::LargeInput));
+    }
+}
+
+criterion_group!(benches, bench_pack, bench_pack_global, bench_subtract_rect);
+criterion_main!(benches);
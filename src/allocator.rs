@@ -0,0 +1,730 @@
+use std::collections::VecDeque;
+
+use RectPacker;
+#[cfg(feature = "serde")]
+use PackerConfig;
+use rect::Rect;
+use size::Size;
+use point::Point;
+use waste::difference;
+
+/// The id of a rectangle allocated by an `AtlasAllocator`, or of a range allocated by an
+/// `IntervalPacker`. Ids of deallocated rectangles are
+/// detected as stale, even when their slot is reused. Ids are never moved to another rectangle, and
+/// are kept when the allocator is serialized and deserialized.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct AllocId {
+    slot: usize,
+    generation: u32,
+}
+
+impl AllocId {
+    /// Creates an id from its slot and the number of times the slot has been reused, e.g. to
+    /// allocate a rectangle with a chosen id by `AtlasAllocator::allocate_with_id`
+    pub fn new(slot: usize, generation: u32) -> AllocId {
+        AllocId{slot: slot, generation: generation}
+    }
+
+    /// The slot of the id
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    /// The number of times the slot of the id had been reused when it was allocated
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+/// A rectangle allocated by an `AtlasAllocator`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Allocation {
+    /// The id to deallocate the rectangle with
+    pub id: AllocId,
+    /// The allocated rectangle
+    pub rectangle: Rect<u32>,
+}
+
+/// An allocated rectangle moved by `AtlasAllocator::compact`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct AllocationMove {
+    /// The id of the rectangle, which is kept
+    pub id: AllocId,
+    /// The rectangle before it was moved
+    pub from: Rect<u32>,
+    /// The rectangle after it was moved
+    pub to: Rect<u32>,
+}
+
+/// The outcome of compacting an `AtlasAllocator` into a smaller atlas.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Compaction {
+    /// The size of the compacted atlas
+    pub size: Size<u32>,
+    /// Every allocated rectangle that was moved
+    pub moves: Vec<AllocationMove>,
+}
+
+/// A slot for an allocated value, with the number of times it has been reused. `latest` is the
+/// latest generation allocated in the slot, which can be later than that of a rectangle restored
+/// by undoing its deallocation.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+struct Slot<R> {
+    generation: u32,
+    latest: u32,
+    #[cfg_attr(feature = "serde", serde(rename = "rect"))]
+    value: Option<R>,
+}
+
+/// The slots of the values allocated by an `AtlasAllocator` or an `IntervalPacker`, which hand
+/// out the ids of the slots. Slots are reused once vacant, and the generation in an id tells
+/// whether it is stale.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SlotTable<R> {
+    slots: Vec<Slot<R>>,
+    vacant: Vec<usize>,
+}
+
+impl<R> SlotTable<R> {
+    /// Creates a table with no slots
+    pub fn new() -> SlotTable<R> {
+        SlotTable{slots: Vec::new(), vacant: Vec::new()}
+    }
+
+    /// Whether no slot holds a value
+    pub fn is_empty(&self) -> bool {
+        self.vacant.len() == self.slots.len()
+    }
+
+    /// Puts a value in a vacant slot, or in a new one if none is vacant, and returns its id
+    pub fn insert(&mut self, value: R) -> AllocId {
+        let slot = match self.vacant.pop() {
+            Some(slot) => {
+                let slot_ref = &mut self.slots[slot];
+                slot_ref.latest = slot_ref.generation;
+                slot_ref.value = Some(value);
+                slot
+            }
+            None => {
+                self.slots.push(Slot{generation: 0, latest: 0, value: Some(value)});
+                self.slots.len() - 1
+            }
+        };
+        AllocId{slot: slot, generation: self.slots[slot].generation}
+    }
+
+    /// The value with an id, or `None` if it has been removed
+    pub fn get(&self, id: AllocId) -> Option<&R> {
+        self.slots.get(id.slot)
+            .filter(|x| x.generation == id.generation)
+            .and_then(|x| x.value.as_ref())
+    }
+
+    /// Removes the value with an id, so that the id becomes stale and the slot vacant, and
+    /// returns it. Returns `None` if it has already been removed.
+    pub fn remove(&mut self, id: AllocId) -> Option<R> {
+        if self.get(id).is_none() {
+            return None;
+        }
+
+        let slot = &mut self.slots[id.slot];
+        slot.generation = slot.latest.wrapping_add(1);
+        self.vacant.push(id.slot);
+        slot.value.take()
+    }
+
+    /// Removes every value, so that every id becomes stale
+    pub fn clear(&mut self) {
+        for slot in self.slots.iter_mut().filter(|x| x.value.is_some()) {
+            slot.generation = slot.latest.wrapping_add(1);
+            slot.value = None;
+        }
+        self.vacant = (0..self.slots.len()).collect();
+    }
+}
+
+/// A change to the allocated rectangles that `AtlasAllocator::undo` can revert.
+#[derive(Clone, Copy, Debug)]
+enum Change {
+    Allocated(AllocId),
+    Deallocated(AllocId, Rect<u32>),
+}
+
+/// An allocator of rectangles in a texture atlas that can also be deallocated, shaped like the
+/// allocators of the `guillotiere` crate so that they can be swapped for it.
+///
+/// With the `serde` feature enabled, the allocator can be serialized, e.g. to persist an atlas
+/// between runs. Its options, bins and allocations are kept, so the ids held by the application
+/// remain valid once it is deserialized, and stale ids remain stale. The history of changes to
+/// undo is not kept.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "AtlasState", from = "AtlasState"))]
+pub struct AtlasAllocator {
+    packer: RectPacker<u32>,
+    blank: RectPacker<u32>,
+    area: u64,
+    slots: SlotTable<Rect<u32>>,
+    history: VecDeque<Change>,
+    undo_limit: usize,
+}
+
+impl AtlasAllocator {
+    /// The number of unused slots `allocate_with_id` may skip past the slots in use, which
+    /// bounds the memory taken by an id from e.g. a corrupted save
+    pub const MAX_SLOT_GAP: usize = 1024;
+
+    /// Creates an allocator for an atlas of the given size
+    pub fn new<Z>(size: Z) -> AtlasAllocator where Z: Into<Size<u32>> {
+        let size = size.into();
+        AtlasAllocator::with_packer(RectPacker::builder().bin(0, 0, size.width, size.height).build())
+    }
+
+    /// Creates an allocator allocating from the free rectangles of `packer`, with its options
+    pub fn with_packer(packer: RectPacker<u32>) -> AtlasAllocator {
+        let mut blank = packer.clone();
+        blank.recording = None;
+        AtlasAllocator{
+            area: bin_area(&blank),
+            blank: blank,
+            packer: packer,
+            slots: SlotTable::new(),
+            history: VecDeque::new(),
+            undo_limit: 0,
+        }
+    }
+
+    /// The packer allocating the rectangles
+    pub fn packer(&self) -> &RectPacker<u32> {
+        &self.packer
+    }
+
+    /// The size of the bounds of the atlas
+    pub fn size(&self) -> Size<u32> {
+        self.packer.bounds().map_or(Size::new(0, 0), |x| x.size())
+    }
+
+    /// Whether no rectangles are allocated
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// The total area of the allocated rectangles
+    pub fn allocated_space(&self) -> u64 {
+        self.packer.packed_area().map_or(0, |&x| x as u64)
+    }
+
+    /// The total area of the atlas that is not allocated
+    pub fn free_space(&self) -> u64 {
+        self.area - self.allocated_space()
+    }
+
+    /// Allocates a rectangle of the given size, or returns `None` if there is no room for it
+    pub fn allocate<Z>(&mut self, size: Z) -> Option<Allocation> where Z: Into<Size<u32>> {
+        let size = size.into();
+        let rect = match self.packer.pack(size.width, size.height) {
+            Some(rect) => rect,
+            None => return None,
+        };
+
+        let id = self.slots.insert(rect);
+        self.record(Change::Allocated(id));
+        Some(Allocation{id: id, rectangle: rect})
+    }
+
+    /// Allocates a rectangle of the given size with the given id, e.g. one chosen by the
+    /// application. Returns `None` if there is no room for it, if the slot of the id holds an
+    /// allocated rectangle, or if its generation is earlier than the next generation of the slot,
+    /// so that stale ids stay stale. Slots are numbered from 0, and a slot more than
+    /// `MAX_SLOT_GAP` past the slots in use is rejected too.
+    pub fn allocate_with_id<Z>(&mut self, id: AllocId, size: Z) -> Option<Allocation> where Z: Into<Size<u32>> {
+        let slots = &self.slots.slots;
+        let usable = match slots.get(id.slot) {
+            Some(slot) => slot.value.is_none() && slot.generation <= id.generation,
+            None => id.slot - slots.len() <= AtlasAllocator::MAX_SLOT_GAP,
+        };
+        if !usable {
+            return None;
+        }
+
+        let size = size.into();
+        let rect = match self.packer.pack(size.width, size.height) {
+            Some(rect) => rect,
+            None => return None,
+        };
+
+        let slots = &mut self.slots;
+        while slots.slots.len() <= id.slot {
+            slots.vacant.push(slots.slots.len());
+            slots.slots.push(Slot{generation: 0, latest: 0, value: None});
+        }
+        slots.vacant.retain(|&x| x != id.slot);
+        slots.slots[id.slot] = Slot{generation: id.generation, latest: id.generation, value: Some(rect)};
+
+        self.record(Change::Allocated(id));
+        Some(Allocation{id: id, rectangle: rect})
+    }
+
+    /// Whether a rectangle of the given size could be allocated if nothing else were
+    pub fn fits_empty<Z>(&self, size: Z) -> bool where Z: Into<Size<u32>> {
+        let size = size.into();
+        self.blank.clone().pack(size.width, size.height).is_some()
+    }
+
+    /// The rectangle allocated with an id, or `None` if it has been deallocated
+    pub fn get(&self, id: AllocId) -> Option<Rect<u32>> {
+        self.slots.get(id).cloned()
+    }
+
+    /// Deallocates a rectangle, so that its space can be allocated again. Does nothing if it has
+    /// already been deallocated.
+    pub fn deallocate(&mut self, id: AllocId) {
+        if let Some(rect) = self.get(id) {
+            self.record(Change::Deallocated(id, rect));
+            self.release(id);
+        }
+    }
+
+    /// Deallocates every rectangle. This cannot be undone, and clears the history of changes.
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        let blank = self.blank.clone();
+        self.reset(blank);
+        self.history.clear();
+    }
+
+    /// Repacks the allocated rectangles into the smallest atlas with the same aspect ratio as the
+    /// current one that they fit in, e.g. to reclaim memory from a sparse atlas. The width is
+    /// found by a binary search, assuming that a narrower atlas is never easier to fit into.
+    /// The atlas and the result change as for `compact_to`, or if the rectangles cannot even be
+    /// repacked at the current size, `None` is returned and the atlas is left as it is.
+    pub fn compact(&mut self) -> Option<Compaction> {
+        let size = self.size();
+        let height = |width: u32| ((width as u64 * size.height as u64 + size.width as u64 - 1) / size.width.max(1) as u64) as u32;
+
+        let mut best = match self.repack(&size) {
+            Some(repacked) => repacked,
+            None => return None,
+        };
+        let (mut low, mut high) = (1, size.width);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.repack(&Size::new(mid, height(mid))) {
+                Some(repacked) => {
+                    high = mid;
+                    best = repacked;
+                },
+                None => low = mid + 1,
+            }
+        }
+
+        Some(self.apply(best))
+    }
+
+    /// Repacks the allocated rectangles into the smallest of the given atlas sizes by area that
+    /// they fit in, such as the texture sizes supported by a GPU. The atlas takes on the new size
+    /// as its only bin, at the origin of its current bounds. Returns the new size and the moved
+    /// rectangles, so that their contents can be copied from the old atlas, or `None` if they fit
+    /// in none of the sizes, leaving the atlas as it is.
+    ///
+    /// The ids of the rectangles are kept, but the history of changes to undo is cleared.
+    pub fn compact_to<Z>(&mut self, sizes: &[Z]) -> Option<Compaction> where Z: Clone + Into<Size<u32>> {
+        let mut sizes: Vec<Size<u32>> = sizes.iter().map(|x| x.clone().into()).collect();
+        sizes.sort_by_key(|x| x.width as u64 * x.height as u64);
+
+        for size in sizes.iter() {
+            if let Some(repacked) = self.repack(size) {
+                return Some(self.apply(repacked));
+            }
+        }
+        None
+    }
+
+    /// Globally packs the allocated rectangles into a copy of the blank packer with a bin of the
+    /// given size instead, returning the new blank packer, the packer and the new rectangle of
+    /// every slot, if they fit
+    fn repack(&self, size: &Size<u32>) -> Option<(RectPacker<u32>, RectPacker<u32>, Vec<(usize, Rect<u32>)>)> {
+        let origin = self.blank.bounds().map_or(Point::new(0, 0), |x| x.min);
+        let bin = Rect::with_size(origin, *size);
+        let mut blank = self.blank.clone();
+        blank.clear();
+        blank.add_free(bin.min, bin.max);
+
+        let mut packer = blank.clone();
+        let allocated = self.slots.slots.iter().enumerate().filter_map(|(slot, x)| x.value.map(|rect| (slot, rect.size())));
+        let packed = match packer.pack_global(allocated, |&(_, size)| size) {
+            Ok(packed) => packed,
+            Err(_) => return None,
+        };
+        Some((blank, packer, packed.into_iter().map(|((slot, _), rect)| (slot, rect)).collect()))
+    }
+
+    /// Takes on the result of `repack`
+    fn apply(&mut self, repacked: (RectPacker<u32>, RectPacker<u32>, Vec<(usize, Rect<u32>)>)) -> Compaction {
+        let (blank, packer, packed) = repacked;
+        let mut moves = Vec::new();
+        for (slot, rect) in packed.into_iter() {
+            let slot_ref = &mut self.slots.slots[slot];
+            let from = slot_ref.value.unwrap();
+            if from != rect {
+                let id = AllocId{slot: slot, generation: slot_ref.generation};
+                moves.push(AllocationMove{id: id, from: from, to: rect});
+            }
+            slot_ref.value = Some(rect);
+        }
+
+        self.area = bin_area(&blank);
+        self.blank = blank;
+        self.reset(packer);
+        self.history.clear();
+        Compaction{size: self.size(), moves: moves}
+    }
+
+    /// Sets the number of allocations and deallocations that can be undone by `undo`, e.g. for
+    /// an editor letting the user take back placing a sprite. Older changes are forgotten. The
+    /// limit is 0 by default, keeping no history.
+    pub fn set_undo_limit(&mut self, limit: usize) {
+        self.undo_limit = limit;
+        while self.history.len() > limit {
+            self.history.pop_front();
+        }
+    }
+
+    /// The number of changes that can be undone, as set by `set_undo_limit`
+    pub fn undo_limit(&self) -> usize {
+        self.undo_limit
+    }
+
+    /// Reverts the latest allocation or deallocation that has not already been undone. An
+    /// undone allocation is deallocated, so its id becomes stale, while an undone deallocation
+    /// restores the rectangle at the same position and with the same id. Returns whether there
+    /// was anything to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop_back() {
+            Some(Change::Allocated(id)) => self.release(id),
+            Some(Change::Deallocated(id, rect)) => {
+                self.slots.vacant.retain(|&x| x != id.slot);
+                let slot = &mut self.slots.slots[id.slot];
+                slot.generation = id.generation;
+                slot.value = Some(rect);
+                self.occupy(&rect);
+            }
+            None => return false,
+        }
+        true
+    }
+
+    /// Adds a change to the history, forgetting the oldest change if the history is full
+    fn record(&mut self, change: Change) {
+        if self.undo_limit == 0 {
+            return;
+        }
+        if self.history.len() == self.undo_limit {
+            self.history.pop_front();
+        }
+        self.history.push_back(change);
+    }
+
+    /// Deallocates an allocated rectangle without recording it
+    fn release(&mut self, id: AllocId) {
+        let rect = self.slots.remove(id).unwrap();
+        self.vacate(&rect);
+    }
+
+    /// Takes the space of an allocated rectangle, including its padding, from the free
+    /// rectangles
+    fn occupy(&mut self, rect: &Rect<u32>) {
+        let padded = self.packer.padded(&rect.size());
+        self.packer.subtract_rect(&Rect::with_size(rect.min, padded));
+        self.packer.refresh_cache();
+        self.packer.packed_area = Some(self.packer.packed_area.unwrap_or(0) + rect.area());
+    }
+
+    /// Makes the space of a deallocated rectangle free again. Adding it as a free rectangle would
+    /// not merge it with the free space around it, so space would fragment as rectangles are
+    /// deallocated. Instead, the free rectangles that can be extended into it are found again.
+    ///
+    /// Only free rectangles intersecting the freed space can be new, and each of those lies
+    /// within the bounding box of the freed space and the free rectangles touching it. They are
+    /// found by subtracting the allocations in that box from the parts of the bins within it,
+    /// rather than by rebuilding the free rectangles of the whole atlas.
+    fn vacate(&mut self, rect: &Rect<u32>) {
+        let freed = Rect::with_size(rect.min, self.packer.padded(&rect.size()));
+        let touching = |x: &Rect<u32>| x.min.x <= freed.max.x && freed.min.x <= x.max.x &&
+                                       x.min.y <= freed.max.y && freed.min.y <= x.max.y;
+        let window = self.packer.free_rects().iter().filter(|x| touching(x)).fold(freed, |a, x| a.union(x));
+
+        let allocated: Vec<Rect<u32>> = self.slots.slots.iter()
+            .filter_map(|x| x.value)
+            .map(|x| Rect::with_size(x.min, self.packer.padded(&x.size())))
+            .filter(|x| x.intersects(&window))
+            .collect();
+        for bin in self.blank.free_rects().iter().filter_map(|x| x.intersection(&window)) {
+            let mut local = RectPacker::with_config(self.packer.config().clone());
+            local.add_free(bin.min, bin.max);
+            for padded in allocated.iter() {
+                local.subtract_rect(padded);
+            }
+            for free in local.free_rects().iter().filter(|x| x.intersects(&freed)) {
+                self.packer.insert_free(*free);
+            }
+        }
+        self.packer.truncate_free();
+        self.packer.update_peak();
+        self.packer.refresh_cache();
+        self.packer.packed_area = match self.packer.packed_area {
+            Some(area) if area > rect.area() => Some(area - rect.area()),
+            _ => None,
+        };
+    }
+
+    /// Replaces the packer, handing its observer and recording to the new packer
+    fn reset(&mut self, mut packer: RectPacker<u32>) {
+        packer.observer.set(self.packer.take_observer());
+        packer.recording = self.packer.recording.take();
+        self.packer = packer;
+    }
+}
+
+/// The area a packer with nothing packed can pack into. Its free rectangles may overlap, and
+/// with a kerf they extend past the bounds.
+fn bin_area(blank: &RectPacker<u32>) -> u64 {
+    let bounds = match blank.bounds() {
+        Some(bounds) => bounds,
+        None => return 0,
+    };
+    let mut disjoint: Vec<Rect<u32>> = Vec::new();
+    for rect in blank.free_rects().iter().filter_map(|x| x.intersection(bounds)) {
+        let mut parts = vec![rect];
+        for covered in disjoint.iter() {
+            parts = parts.iter().flat_map(|x| difference(x, covered)).collect();
+        }
+        disjoint.extend(parts);
+    }
+    disjoint.iter().map(|x| x.width() as u64 * x.height() as u64).sum()
+}
+
+/// The state an `AtlasAllocator` is serialized as. The free rectangles are not kept, but are
+/// recomputed from the bins and the allocated rectangles.
+#[cfg(feature = "serde")]
+#[derive(Clone, Debug, ::serde::Serialize, ::serde::Deserialize)]
+struct AtlasState {
+    config: PackerConfig<u32>,
+    max_free: Option<usize>,
+    bounds: Option<Rect<u32>>,
+    bins: Vec<Rect<u32>>,
+    slots: Vec<Slot<Rect<u32>>>,
+}
+
+#[cfg(feature = "serde")]
+impl From<AtlasAllocator> for AtlasState {
+    fn from(atlas: AtlasAllocator) -> AtlasState {
+        AtlasState{
+            config: atlas.blank.config().clone(),
+            max_free: atlas.blank.max_free(),
+            bounds: atlas.blank.bounds().cloned(),
+            bins: atlas.blank.free_rects().to_vec(),
+            slots: atlas.slots.slots,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<AtlasState> for AtlasAllocator {
+    fn from(state: AtlasState) -> AtlasAllocator {
+        let mut blank = RectPacker::with_config(state.config);
+        blank.set_max_free(state.max_free);
+        for bin in state.bins.iter() {
+            blank.add_free(bin.min, bin.max);
+        }
+        blank.bounds = state.bounds;
+
+        let mut atlas = AtlasAllocator::with_packer(blank);
+        let slots = state.slots;
+        let vacant = (0..slots.len()).filter(|&x| slots[x].value.is_none()).collect();
+        let allocated: Vec<Rect<u32>> = slots.iter().filter_map(|x| x.value).collect();
+        atlas.slots = SlotTable{slots: slots, vacant: vacant};
+        for rect in allocated.iter() {
+            atlas.occupy(rect);
+        }
+        atlas
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AtlasAllocator, AllocId};
+    use rect::Rect;
+    use size::Size;
+
+    #[test]
+    fn allocation() {
+        let mut atlas = AtlasAllocator::new((20,10));
+        let a = atlas.allocate((10,10)).unwrap();
+        let b = atlas.allocate((10,10)).unwrap();
+        assert_eq!(b.rectangle, Rect::new((10,0), (20,10)));
+        assert_eq!(atlas.allocate((1,1)), None);
+        assert_eq!((atlas.allocated_space(), atlas.free_space()), (200, 0));
+
+        atlas.deallocate(a.id);
+        atlas.deallocate(a.id);
+        assert_eq!(atlas.get(a.id), None);
+        let c = atlas.allocate((5,5)).unwrap();
+        assert!(c.id != a.id && atlas.get(c.id).is_some());
+
+        atlas.clear();
+        assert!(atlas.is_empty() && atlas.get(b.id).is_none());
+        assert_eq!(atlas.size(), Size::new(20,10));
+        assert_eq!(atlas.allocate((20,10)).map(|x| x.rectangle), Some(Rect::new((0,0), (20,10))));
+    }
+
+    #[test]
+    fn allocation_with_id() {
+        let mut atlas = AtlasAllocator::new((20,10));
+        let a = atlas.allocate_with_id(AllocId::new(3, 7), (10,10)).unwrap();
+        assert_eq!((a.id.slot(), a.id.generation()), (3, 7));
+        assert_eq!(atlas.allocate_with_id(AllocId::new(3, 8), (5,5)), None);
+        assert_eq!(atlas.get(AllocId::new(3, 6)), None);
+
+        let b = atlas.allocate((5,5)).unwrap();
+        assert!(b.id.slot() < 3);
+        atlas.deallocate(a.id);
+        assert_eq!(atlas.get(a.id), None);
+        assert_eq!(atlas.allocate_with_id(a.id, (10,10)), None);
+        assert!(atlas.allocate_with_id(AllocId::new(3, 8), (10,10)).is_some());
+        assert_eq!((atlas.get(a.id), atlas.get(b.id)), (None, Some(b.rectangle)));
+
+        assert_eq!(atlas.allocate_with_id(AllocId::new(usize::MAX, 0), (1,1)), None);
+        assert_eq!(atlas.allocate_with_id(AllocId::new(4 + AtlasAllocator::MAX_SLOT_GAP + 1, 0), (1,1)), None);
+        assert!(atlas.allocate_with_id(AllocId::new(4 + AtlasAllocator::MAX_SLOT_GAP, 0), (1,1)).is_some());
+    }
+
+    #[test]
+    fn deallocation() {
+        use std::sync::{Arc, Mutex};
+        use observer::Observer;
+        use RectPacker;
+
+        struct Placed(Arc<Mutex<usize>>);
+        impl Observer<u32> for Placed {
+            fn on_place(&mut self, _: &Rect<u32>) { *self.0.lock().unwrap() += 1; }
+        }
+
+        let placed = Arc::new(Mutex::new(0));
+        let mut packer = RectPacker::builder().bin(0,0,32,32).max_free(4).build();
+        packer.set_observer(Placed(placed.clone()));
+        let mut atlas = AtlasAllocator::with_packer(packer);
+        let ids: Vec<_> = (0..16).map(|_| atlas.allocate((8,8)).unwrap()).collect();
+        for x in ids.iter().step_by(3) {
+            atlas.deallocate(x.id);
+            assert!(atlas.packer().free_rects().len() <= 4);
+        }
+        assert!(atlas.allocate((8,8)).is_some());
+        assert_eq!(*placed.lock().unwrap(), 17);
+
+        atlas.clear();
+        assert_eq!(atlas.packer().free_rects(), &[Rect::new((0,0), (32,32))]);
+        atlas.allocate((8,8)).unwrap();
+        assert_eq!(*placed.lock().unwrap(), 18);
+
+        // Deallocating everything merges the free space back into the bin
+        let mut atlas = AtlasAllocator::new((20,20));
+        let ids: Vec<_> = (0..16).map(|_| atlas.allocate((5,5)).unwrap()).collect();
+        for x in ids.iter().step_by(2).chain(ids.iter().skip(1).step_by(2)) {
+            atlas.deallocate(x.id);
+        }
+        assert_eq!(atlas.packer().free_rects(), &[Rect::new((0,0), (20,20))]);
+        assert_eq!(atlas.free_space(), 400);
+
+        // Overlapping bins are only counted once
+        let packer = RectPacker::builder().bin(0,0,10,10).bin(5,0,10,10).build();
+        let mut atlas = AtlasAllocator::with_packer(packer);
+        assert_eq!(atlas.free_space(), 150);
+        let a = atlas.allocate((10,10)).unwrap();
+        assert_eq!(atlas.free_space(), 50);
+        atlas.deallocate(a.id);
+        assert_eq!(atlas.free_space(), 150);
+        assert_eq!(atlas.packer().free_rects().len(), 2);
+
+        // Bins extended by a kerf keep their bounds
+        let packer = RectPacker::builder().bin(0,0,10,10).kerf(1).build();
+        let mut atlas = AtlasAllocator::with_packer(packer);
+        let a = atlas.allocate((4,10)).unwrap();
+        atlas.allocate((5,10)).unwrap();
+        atlas.deallocate(a.id);
+        assert_eq!(atlas.packer().bounds(), Some(&Rect::new((0,0), (10,10))));
+        assert_eq!(atlas.free_space(), 50);
+        assert_eq!(atlas.allocate((4,10)).map(|x| x.rectangle), Some(a.rectangle));
+    }
+
+    #[test]
+    fn undo_allocation() {
+        let mut atlas = AtlasAllocator::new((20,10));
+        atlas.set_undo_limit(2);
+        let a = atlas.allocate((10,10)).unwrap();
+        let b = atlas.allocate((10,10)).unwrap();
+        atlas.deallocate(a.id);
+
+        assert!(atlas.undo());
+        assert_eq!(atlas.get(a.id), Some(a.rectangle));
+        assert!(atlas.undo());
+        assert_eq!((atlas.get(a.id), atlas.get(b.id)), (Some(a.rectangle), None));
+        assert!(!atlas.undo());
+
+        // The id of an undone allocation stays stale, even once a restored rectangle in the same
+        // slot is deallocated and the slot is reused
+        atlas.deallocate(a.id);
+        let c = atlas.allocate((10,10)).unwrap();
+        assert_eq!(c.id.slot(), a.id.slot());
+        assert!(atlas.undo() && atlas.undo());
+        atlas.deallocate(a.id);
+        let d = atlas.allocate((10,10)).unwrap();
+        assert_eq!(d.id.slot(), a.id.slot());
+        assert!(d.id != c.id && atlas.get(c.id).is_none() && atlas.get(b.id).is_none());
+    }
+
+    #[test]
+    fn compaction() {
+        let mut atlas = AtlasAllocator::new((64,32));
+        let ids: Vec<_> = (0..8).map(|_| atlas.allocate((8,8)).unwrap()).collect();
+        for x in ids.iter().skip(1).step_by(2) {
+            atlas.deallocate(x.id);
+        }
+
+        let mut sized = atlas.clone();
+        assert_eq!(sized.compact_to(&[(8,8), (16,16), (64,64)]).map(|x| x.size), Some(Size::new(16,16)));
+        assert_eq!(sized.compact_to(&[(8,8)]), None);
+        assert_eq!(sized.size(), Size::new(16,16));
+
+        let compaction = atlas.compact().unwrap();
+        // Two rows of rectangles need a height of 16, and so a width of 31
+        assert_eq!(compaction.size, Size::new(31,16));
+        assert_eq!(atlas.size(), Size::new(31,16));
+        assert_eq!((atlas.allocated_space(), atlas.free_space()), (256, 240));
+        for x in ids.iter().step_by(2) {
+            let moved = compaction.moves.iter().find(|m| m.id == x.id).map_or(x.rectangle, |m| m.to);
+            assert_eq!(atlas.get(x.id), Some(moved));
+        }
+        assert!(compaction.moves.iter().all(|m| m.from != m.to));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn persisted_allocation() {
+        use super::AtlasState;
+
+        let mut atlas = AtlasAllocator::new((20,10));
+        let a = atlas.allocate((10,10)).unwrap();
+        let b = atlas.allocate((5,5)).unwrap();
+        atlas.deallocate(a.id);
+
+        let mut restored = AtlasAllocator::from(AtlasState::from(atlas.clone()));
+        assert_eq!((restored.get(a.id), restored.get(b.id)), (None, Some(b.rectangle)));
+        assert_eq!(restored.free_space(), atlas.free_space());
+        let c = restored.allocate((10,10)).unwrap();
+        assert!(c.id != a.id && !c.rectangle.intersects(&b.rectangle));
+    }
+}
@@ -0,0 +1,151 @@
+use std::cmp::Ordering;
+use std::ops::{Add, Sub, Mul};
+
+use MinMaxIteratorExt;
+
+/// An axis-aligned box defined by a minimum and a maximum coordinate, indexed by axis as x, y
+/// and z. Like `Rect`, it is half-open.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Cuboid<S> {
+    pub min: [S; 3],
+    pub max: [S; 3],
+}
+
+impl<S> Cuboid<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> {
+    /// Creates a box from the position of its minimum coordinate and its size
+    pub fn with_size(position: [S; 3], size: [S; 3]) -> Cuboid<S> {
+        let max = [position[0].clone() + size[0].clone(),
+                   position[1].clone() + size[1].clone(),
+                   position[2].clone() + size[2].clone()];
+        Cuboid{min: position, max: max}
+    }
+
+    /// The extent of the box along an axis
+    pub fn extent(&self, axis: usize) -> S {
+        self.max[axis].clone() - self.min[axis].clone()
+    }
+
+    /// Whether a box intersects another box
+    pub fn intersects(&self, other: &Cuboid<S>) -> bool {
+        (0..3).all(|axis| self.min[axis] < other.max[axis] && self.max[axis] > other.min[axis])
+    }
+
+    /// Determines if a box is a superset of (contains all of) another box
+    pub fn supersets(&self, other: &Cuboid<S>) -> bool {
+        (0..3).all(|axis| self.min[axis] <= other.min[axis] && self.max[axis] >= other.max[axis])
+    }
+}
+
+impl<S> Cuboid<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> {
+    /// The volume of the box
+    pub fn volume(&self) -> S {
+        self.extent(0) * self.extent(1) * self.extent(2)
+    }
+}
+
+/// An experimental packer of boxes into free boxes, generalizing MAXRECTS to three dimensions,
+/// e.g. for packing volumes into 3D textures.
+///
+/// Like `RectPacker`, it keeps a list of maximal free boxes, which placements split in up to six
+/// along the faces of the placed box. A box is placed in the free box where its shortest leftover
+/// side is the shortest, as with `Heuristic::Bssf`. It is never rotated.
+#[derive(Clone, PartialEq, Debug)]
+pub struct BoxPacker<S> {
+    free: Vec<Cuboid<S>>,
+}
+
+impl<S> BoxPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> {
+    /// Creates a box packer with no free boxes
+    pub fn new() -> BoxPacker<S> {
+        BoxPacker{free: Vec::new()}
+    }
+
+    /// The free boxes, none of which contains another
+    pub fn free_boxes(&self) -> &[Cuboid<S>] {
+        &self.free
+    }
+
+    /// Marks a box as free, so that boxes can be packed into it
+    pub fn add_free(&mut self, free: Cuboid<S>) {
+        if !self.free.iter().any(|x| x.supersets(&free)) {
+            self.free.retain(|x| !free.supersets(x));
+            self.free.push(free);
+        }
+    }
+
+    /// Packs a box of the given size into a free box, so that it does not intersect any previously
+    /// packed boxes, and returns it. Returns `None` if it fits in no free box.
+    ///
+    /// Equally good placements are broken in favor of the lowest z, then y and then x coordinate.
+    pub fn pack(&mut self, size: [S; 3]) -> Option<Cuboid<S>> {
+        let compare = |a: &S, b: &S| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+        let best = self.free.iter()
+            .filter(|free| (0..3).all(|axis| size[axis] <= free.extent(axis)))
+            .map(|free| {
+                let leftover = (0..3).map(|axis| free.extent(axis) - size[axis].clone())
+                    .min_cmp(|a, b| compare(a, b))
+                    .unwrap();
+                (leftover, free.min.clone())
+            })
+            .min_cmp(|&(ref a, ref x), &(ref b, ref y)| compare(a, b)
+                .then_with(|| (0..3).rev().map(|axis| compare(&x[axis], &y[axis]))
+                    .find(|&x| x != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)));
+
+        best.map(|(_, position)| {
+            let placed = Cuboid::with_size(position, size);
+            self.subtract(&placed);
+            placed
+        })
+    }
+
+    /// Removes a box from the free boxes, splitting every free box it intersects into the up to
+    /// six maximal boxes around it.
+    fn subtract(&mut self, placed: &Cuboid<S>) {
+        let mut split = Vec::new();
+        self.free.retain(|free| {
+            if !free.intersects(placed) {
+                return true;
+            }
+
+            for axis in 0..3 {
+                if placed.min[axis] > free.min[axis] {
+                    let mut below = free.clone();
+                    below.max[axis] = placed.min[axis].clone();
+                    split.push(below);
+                }
+                if placed.max[axis] < free.max[axis] {
+                    let mut above = free.clone();
+                    above.min[axis] = placed.max[axis].clone();
+                    split.push(above);
+                }
+            }
+            false
+        });
+
+        for free in split.into_iter() {
+            self.add_free(free);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BoxPacker, Cuboid};
+
+    #[test]
+    fn box_pack() {
+        let mut packer = BoxPacker::new();
+        packer.add_free(Cuboid::with_size([0,0,0], [10,10,10]));
+
+        let packed: Vec<_> = (0..8).map(|_| packer.pack([5,5,5]).unwrap()).collect();
+        assert_eq!(packed[0], Cuboid::with_size([0,0,0], [5,5,5]));
+        assert_eq!(packed.iter().map(|x| x.volume()).sum::<u32>(), 1000);
+        for (i, a) in packed.iter().enumerate() {
+            assert!(packed[i + 1..].iter().all(|b| !a.intersects(b)));
+        }
+
+        assert!(packer.free_boxes().is_empty());
+        assert_eq!(packer.pack([1,1,1]), None);
+    }
+}
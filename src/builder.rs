@@ -0,0 +1,139 @@
+use std::ops::{Add, Sub, Mul};
+
+use {RectPacker, Heuristic, PackerConfig, MaybeDebug};
+use rect::Rect;
+use size::Size;
+
+/// A builder for configuring a `RectPacker`, created by `RectPacker::builder`.
+#[derive(Clone, Debug)]
+pub struct RectPackerBuilder<S> {
+    bins: Vec<Rect<S>>,
+    config: PackerConfig<S>,
+    capacity: usize,
+    max_free: Option<usize>,
+    kerf: Option<S>,
+}
+
+impl<S> RectPackerBuilder<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    /// Creates a builder for a packer with no free rectangles and the default options
+    pub fn new() -> RectPackerBuilder<S> {
+        RectPackerBuilder{
+            bins: Vec::new(),
+            config: Default::default(),
+            capacity: 0,
+            max_free: None,
+            kerf: None,
+        }
+    }
+
+    /// Adds a free rectangle with its minimum coordinate at `(x, y)` and the given size, as if
+    /// by `RectPacker::add_free`
+    pub fn bin(mut self, x: S, y: S, width: S, height: S) -> RectPackerBuilder<S> {
+        self.bins.push(Rect::with_size((x, y), (width, height)));
+        self
+    }
+
+    /// Replaces all placement options at once, e.g. to share them between packers
+    pub fn config(mut self, config: PackerConfig<S>) -> RectPackerBuilder<S> {
+        self.config = config;
+        self
+    }
+
+    /// Sets the heuristic used to choose between free rectangles, which is `Heuristic::Bssf` by
+    /// default
+    pub fn heuristic(mut self, heuristic: Heuristic) -> RectPackerBuilder<S> {
+        self.config.heuristic = heuristic;
+        self
+    }
+
+    /// Sets whether rectangles may be rotated by 90 degrees when that gives a better placement.
+    /// Rotated rectangles are returned with their width and height swapped.
+    pub fn allow_rotation(mut self, allow_rotation: bool) -> RectPackerBuilder<S> {
+        self.config.allow_rotation = allow_rotation;
+        self
+    }
+
+    /// Keeps `padding` free to the right of and below every packed rectangle, so that no two
+    /// packed rectangles are closer than that
+    pub fn padding(mut self, padding: S) -> RectPackerBuilder<S> {
+        self.config.padding = Some(padding);
+        self
+    }
+
+    /// Makes every cut through sheet material consume `kerf`, the width of the blade, for
+    /// cutting rather than texture atlases. Packed rectangles are kept `kerf` apart, as with
+    /// padding, which this replaces, except at the far edges of the bins, which need no cut.
+    pub fn kerf(mut self, kerf: S) -> RectPackerBuilder<S> {
+        self.kerf = Some(kerf);
+        self
+    }
+
+    /// Rounds the padded width of every rectangle up to a multiple of `pitch`, see
+    /// `PackerConfig::row_pitch`
+    pub fn row_pitch(mut self, pitch: S) -> RectPackerBuilder<S> {
+        self.config.row_pitch = Some(pitch);
+        self
+    }
+
+    /// Rounds the padded width and height of every rectangle up to multiples of `width` and
+    /// `height`, see `PackerConfig::alignment`
+    pub fn alignment(mut self, width: S, height: S) -> RectPackerBuilder<S> {
+        self.config.alignment = Some(Size::new(width, height));
+        self
+    }
+
+    /// Breaks ties between equally good placements pseudo-randomly using `seed`, see
+    /// `PackerConfig::tie_seed`
+    pub fn tie_seed(mut self, seed: u64) -> RectPackerBuilder<S> {
+        self.config.tie_seed = Some(seed);
+        self
+    }
+
+    /// Reserves space for at least `capacity` free rectangles, as by `RectPacker::with_capacity`
+    pub fn capacity(mut self, capacity: usize) -> RectPackerBuilder<S> {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Limits the number of free rectangles retained, as by `RectPacker::set_max_free`
+    pub fn max_free(mut self, limit: usize) -> RectPackerBuilder<S> {
+        self.max_free = Some(limit);
+        self
+    }
+
+    /// Creates the configured packer
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width or height of a bin is negative
+    pub fn build(self) -> RectPacker<S> {
+        let mut config = self.config;
+        if let Some(ref kerf) = self.kerf {
+            config.padding = Some(kerf.clone());
+        }
+
+        let mut packer = RectPacker::with_capacity(self.capacity);
+        packer.set_config(config);
+        packer.set_max_free(self.max_free);
+
+        let mut bounds: Option<Rect<S>> = None;
+        for bin in self.bins.into_iter() {
+            bounds = Some(match bounds {
+                Some(bounds) => bounds.union(&bin),
+                None => bin.clone(),
+            });
+
+            // The padding after a rectangle at the far edge of a bin is the cut that is not
+            // needed, so it may stick out of the bin.
+            match self.kerf {
+                Some(ref kerf) => packer.add_free(bin.min, (bin.max.x + kerf.clone(), bin.max.y + kerf.clone())),
+                None => packer.add_free(bin.min, bin.max),
+            }
+        }
+
+        if self.kerf.is_some() {
+            packer.bounds = bounds;
+        }
+        packer
+    }
+}
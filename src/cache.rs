@@ -0,0 +1,55 @@
+use std::mem;
+
+use {Candidate, Rect, Size};
+
+/// The number of sizes whose best placement is remembered by a `FitCache`
+const CAPACITY: usize = 16;
+
+/// Remembers the best placement without rotation of the most recently packed sizes, together with
+/// the free rectangles added since the placements were last brought up to date.
+#[derive(Clone, Debug)]
+pub struct FitCache<S> {
+    entries: Vec<(Size<S>, Option<Candidate<S>>)>,
+    added: Vec<Rect<S>>,
+}
+
+impl<S> FitCache<S> where S: PartialEq {
+    pub fn new() -> FitCache<S> {
+        FitCache{entries: Vec::new(), added: Vec::new()}
+    }
+
+    /// The remembered best placement of a size, which is `None` if the size is not remembered and
+    /// `Some(None)` if it is known not to fit
+    pub fn get(&self, size: &Size<S>) -> Option<&Option<Candidate<S>>> {
+        self.entries.iter().find(|&&(ref x, _)| x == size).map(|&(_, ref candidate)| candidate)
+    }
+
+    /// Remembers the best placement of a size, forgetting the least recently remembered size if
+    /// full
+    pub fn insert(&mut self, size: Size<S>, candidate: Option<Candidate<S>>) {
+        if self.entries.len() == CAPACITY {
+            self.entries.remove(0);
+        }
+        self.entries.push((size, candidate));
+    }
+
+    /// Records that a free rectangle has been added
+    pub fn added(&mut self, rect: Rect<S>) {
+        self.added.push(rect);
+    }
+
+    /// Takes the free rectangles added since the last call, and the remembered placements
+    pub fn take(&mut self) -> (Vec<Rect<S>>, Vec<(Size<S>, Option<Candidate<S>>)>) {
+        (mem::replace(&mut self.added, Vec::new()), mem::replace(&mut self.entries, Vec::new()))
+    }
+
+    /// Replaces the remembered placements
+    pub fn restore(&mut self, entries: Vec<(Size<S>, Option<Candidate<S>>)>) {
+        self.entries = entries;
+    }
+
+    /// Forgets every remembered placement
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
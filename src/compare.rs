@@ -0,0 +1,136 @@
+use std::fmt;
+use std::ops::{Add, Sub, Mul};
+use std::time::{Duration, Instant};
+
+use {RectPacker, Heuristic, SortOrder, MaybeDebug, MaybeSync};
+use size::Size;
+
+/// The outcome of packing a set of rectangles with one combination of heuristic and order, as
+/// part of a `HeuristicComparison`.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct ComparisonRow {
+    /// The heuristic used
+    pub heuristic: Heuristic,
+    /// The order the rectangles were packed in one at a time, or `None` if they were packed
+    /// globally by `pack_most`
+    pub order: Option<SortOrder>,
+    /// The number of bins used
+    pub bins: usize,
+    /// The number of rectangles that did not fit in any bin
+    pub unpacked: usize,
+    /// The mean utilization of the bins used
+    pub utilization: f64,
+    /// The time spent packing
+    pub elapsed: Duration,
+}
+
+/// A comparison of every built-in heuristic and order on the same rectangles, as returned by
+/// `RectPacker::compare_heuristics`. It is displayed as a table, one row per combination, and
+/// with the `serde` feature enabled, it can be serialized, e.g. to JSON.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct HeuristicComparison {
+    /// A row for every combination of heuristic and order
+    pub rows: Vec<ComparisonRow>,
+}
+
+impl HeuristicComparison {
+    /// The best combination: the one leaving the fewest rectangles unpacked, then using the fewest
+    /// bins and then with the highest utilization, or the first of those that are equally good.
+    pub fn best(&self) -> Option<&ComparisonRow> {
+        self.rows.iter().fold(None, |best: Option<&ComparisonRow>, row| match best {
+            Some(best) if (best.unpacked, best.bins) < (row.unpacked, row.bins) => Some(best),
+            Some(best) if (best.unpacked, best.bins) == (row.unpacked, row.bins) && best.utilization >= row.utilization => Some(best),
+            _ => Some(row),
+        })
+    }
+}
+
+impl fmt::Display for HeuristicComparison {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        writeln!(formatter, "{:<10} {:<13} {:>4} {:>8} {:>11} {:>10}", "heuristic", "order", "bins", "unpacked", "utilization", "time")?;
+        for row in self.rows.iter() {
+            let order = row.order.map_or("Global".to_string(), |x| format!("{:?}", x));
+            writeln!(formatter, "{:<10} {:<13} {:>4} {:>8} {:>10.1}% {:>8.3}ms",
+                     format!("{:?}", row.heuristic), order, row.bins, row.unpacked, row.utilization * 100.0,
+                     row.elapsed.as_secs_f64() * 1000.0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<S> RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug + MaybeSync + Into<f64> {
+    /// Packs rectangles of the given sizes with every built-in heuristic, both globally and one
+    /// at a time in every `SortOrder`, to help choosing a configuration empirically. Every bin is
+    /// a copy of this packer with its other options, and rectangles that do not fit are packed
+    /// into another bin, up to `max_bins` bins.
+    pub fn compare_heuristics<Z>(&self, sizes: &[Z], max_bins: usize) -> HeuristicComparison where Z: Clone + Into<Size<S>> {
+        let heuristics = [Heuristic::Bssf, Heuristic::Blsf, Heuristic::Baf, Heuristic::BottomLeft];
+        let orders = [None, Some(SortOrder::AreaDesc), Some(SortOrder::ShortSideDesc),
+                      Some(SortOrder::LongSideDesc), Some(SortOrder::PerimeterDesc)];
+        let sizes: Vec<Size<S>> = sizes.iter().map(|x| x.clone().into()).collect();
+
+        let mut rows = Vec::new();
+        for &heuristic in heuristics.iter() {
+            for &order in orders.iter() {
+                let start = Instant::now();
+                let mut remaining = sizes.clone();
+                let mut utilization = Vec::new();
+                while !remaining.is_empty() && utilization.len() < max_bins {
+                    let mut bin = self.clone();
+                    let mut config = bin.config().clone();
+                    config.heuristic = heuristic;
+                    bin.set_config(config);
+
+                    let leftover = match order {
+                        None => bin.pack_most(remaining, |x| x.clone()).1,
+                        Some(order) => match bin.pack_sorted(remaining, order, |x| x.clone()) {
+                            Ok(_) => Vec::new(),
+                            Err(failed) => failed.into_parts().1,
+                        },
+                    };
+
+                    if bin.packed_area().is_none() {
+                        remaining = leftover;
+                        break;
+                    }
+                    utilization.push(bin.utilization());
+                    remaining = leftover;
+                }
+
+                rows.push(ComparisonRow{
+                    heuristic: heuristic,
+                    order: order,
+                    bins: utilization.len(),
+                    unpacked: remaining.len(),
+                    utilization: if utilization.is_empty() { 0.0 } else { utilization.iter().sum::<f64>() / utilization.len() as f64 },
+                    elapsed: start.elapsed(),
+                });
+            }
+        }
+
+        HeuristicComparison{rows: rows}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {RectPacker, Heuristic, SortOrder};
+
+    #[test]
+    fn heuristic_comparison() {
+        let packer = RectPacker::builder().bin(0,0,10,10).build();
+        let comparison = packer.compare_heuristics(&[(10,10), (5,10), (5,5), (5,5), (20,1)], 4);
+
+        assert_eq!(comparison.rows.len(), 20);
+        assert!(comparison.rows.iter().all(|x| x.unpacked == 1));
+        let best = comparison.best().unwrap();
+        assert_eq!((best.heuristic, best.order), (Heuristic::Bssf, Some(SortOrder::AreaDesc)));
+        assert_eq!((best.bins, best.utilization), (2, 1.0));
+
+        let table = comparison.to_string();
+        assert_eq!(table.lines().count(), 21);
+        assert!(table.lines().nth(1).unwrap().starts_with("Bssf       Global           3        1       66.7%"));
+    }
+}
@@ -0,0 +1,154 @@
+use std::ops::{Add, Sub, Mul};
+use std::sync::{Arc, Mutex};
+
+use {RectPacker, PackerConfig, Stats, MaybeDebug};
+use rect::Rect;
+use size::Size;
+use point::Point;
+
+/// A packer that can be shared between threads, e.g. threads streaming textures into an atlas,
+/// without serializing every search for a placement.
+///
+/// Placements are searched for in a snapshot of the free rectangles without holding the lock,
+/// and the lock is only taken to place a rectangle. If another thread used the chosen free
+/// rectangle in the meantime, the search is repeated. A placement is thus always valid, but may
+/// not be the best one by heuristic if several threads pack at once.
+///
+/// The snapshot is only taken when it is needed after the free rectangles changed, so placing
+/// several rectangles between two searches copies the free rectangles once. Packing through a
+/// `ConcurrentRectPacker` is not recorded.
+#[derive(Debug)]
+pub struct ConcurrentRectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    config: PackerConfig<S>,
+    /// The packer, and a snapshot of its free rectangles shared with the threads searching them,
+    /// if it was taken since they last changed
+    state: Mutex<(RectPacker<S>, Option<Arc<Vec<Rect<S>>>>)>,
+}
+
+impl<S> ConcurrentRectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    /// Shares a packer between threads, with its current options
+    pub fn new(packer: RectPacker<S>) -> ConcurrentRectPacker<S> {
+        ConcurrentRectPacker{config: packer.config().clone(), state: Mutex::new((packer, None))}
+    }
+
+    /// Returns the packer, no longer shared
+    pub fn into_inner(self) -> RectPacker<S> {
+        self.state.into_inner().unwrap().0
+    }
+
+    /// A snapshot of the free rectangles
+    pub fn free_rects(&self) -> Arc<Vec<Rect<S>>> {
+        self.snapshot().1
+    }
+
+    /// A snapshot of the free rectangles, taking it if they changed since the last one, and the
+    /// number of rectangles placed when it was taken
+    fn snapshot(&self) -> (u64, Arc<Vec<Rect<S>>>) {
+        let mut state = self.state.lock().unwrap();
+        let (ref packer, ref mut free) = *state;
+        let free = free.get_or_insert_with(|| Arc::new(packer.free_rects().to_vec()));
+        (packer.placements, free.clone())
+    }
+
+    /// The counters of the packer, see `RectPacker::stats`
+    pub fn stats(&self) -> Stats {
+        self.state.lock().unwrap().0.stats()
+    }
+
+    /// Marks a rectangle as free, as by `RectPacker::add_free`
+    pub fn add_free<P>(&self, min: P, max: P) where P: Into<Point<S>> {
+        let mut state = self.state.lock().unwrap();
+        state.0.add_free(min, max);
+        state.1 = None;
+    }
+
+    /// Packs a rectangle as `RectPacker::pack` does, except that the placement may not be the best
+    /// one if other threads are packing at the same time
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width or height is negative
+    pub fn pack(&self, width: S, height: S) -> Option<Rect<S>> {
+        let size = Size::new(width, height);
+        if let Some(placed) = self.state.lock().unwrap().0.admit(&size) {
+            return placed;
+        }
+
+        loop {
+            let (placements, free) = self.snapshot();
+            let candidate = self.config.best_fit(placements, &free, &size);
+
+            let mut state = self.state.lock().unwrap();
+            state.0.stats.heuristic_evaluations += free.len() * self.config.orientations(&size);
+
+            match candidate {
+                Some(candidate) => if state.0.is_free(&candidate.free) {
+                    let (rect, _) = state.0.place(candidate);
+                    state.1 = None;
+                    return Some(rect);
+                },
+                None => return None,
+            }
+
+            trace!("free rectangle taken while searching, retrying");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use super::ConcurrentRectPacker;
+    use {RectPacker, PackerConfig, ZeroSizePolicy};
+
+    #[test]
+    fn concurrent_pack() {
+        let packer = ConcurrentRectPacker::new(RectPacker::builder().bin(0,0,100,100).build());
+
+        let packed: Vec<_> = thread::scope(|scope| {
+            let threads: Vec<_> = (0..4).map(|_| scope.spawn(|| {
+                (0..25).map(|_| packer.pack(10,10).unwrap()).collect::<Vec<_>>()
+            })).collect();
+            threads.into_iter().flat_map(|x| x.join().unwrap()).collect()
+        });
+
+        for (i, a) in packed.iter().enumerate() {
+            assert!(packed[i + 1..].iter().all(|b| !a.intersects(b)));
+        }
+        assert_eq!(packer.pack(1,1), None);
+        assert_eq!(packer.stats().packs_attempted, 101);
+        assert!(packer.into_inner().free_rects().is_empty());
+    }
+
+    #[test]
+    fn concurrent_empty_pack() {
+        for &policy in [ZeroSizePolicy::Pack, ZeroSizePolicy::Reject, ZeroSizePolicy::Skip, ZeroSizePolicy::Origin].iter() {
+            let config = PackerConfig{zero_size: policy, ..PackerConfig::default()};
+            let mut sequential = RectPacker::builder().bin(0,0,10,10).config(config).build();
+            let concurrent = ConcurrentRectPacker::new(sequential.clone());
+            assert_eq!(concurrent.pack(0,5), sequential.pack(0,5));
+            assert_eq!(concurrent.pack(5,5), sequential.pack(5,5));
+            assert_eq!(concurrent.stats(), sequential.stats());
+            assert_eq!(concurrent.into_inner(), sequential);
+        }
+    }
+
+    #[test]
+    fn concurrent_tie_seed() {
+        let config = PackerConfig{tie_seed: Some(7), allow_rotation: true, ..PackerConfig::default()};
+        let mut sequential = RectPacker::builder().bin(0,0,40,40).config(config).build();
+        let concurrent = ConcurrentRectPacker::new(sequential.clone());
+        for &(width, height) in [(10,10), (5,8), (10,10), (8,5), (20,3), (10,10)].iter() {
+            assert_eq!(concurrent.pack(width, height), sequential.pack(width, height));
+        }
+        assert_eq!(&concurrent.free_rects()[..], sequential.free_rects());
+    }
+
+    #[test]
+    #[should_panic]
+    fn concurrent_negative_pack() {
+        let packer = ConcurrentRectPacker::new(RectPacker::builder().bin(0,0,10,10).build());
+        packer.pack(-5,5);
+    }
+}
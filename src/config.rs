@@ -0,0 +1,162 @@
+use std::ops::{Add, Sub};
+
+use round_up;
+use heuristic::Heuristic;
+use size::Size;
+
+/// How rectangles with a width or height of zero, such as the glyphs of spaces, are packed.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub enum ZeroSizePolicy {
+    /// Packed like any other rectangle, taking up any padding or alignment
+    Pack,
+    /// Never packed: `pack` returns `None`, and global packing fails without packing anything
+    Reject,
+    /// Never packed, but not in the way of other rectangles: global packing packs the others and
+    /// fails with only the empty rectangles left over if all others were packed
+    Skip,
+    /// Placed at the minimum corner of the bounds without taking up any space
+    Origin,
+}
+
+#[cfg(feature = "quickcheck")]
+impl ::quickcheck::Arbitrary for ZeroSizePolicy {
+    fn arbitrary(g: &mut ::quickcheck::Gen) -> ZeroSizePolicy {
+        *g.choose(&[ZeroSizePolicy::Pack, ZeroSizePolicy::Reject, ZeroSizePolicy::Skip, ZeroSizePolicy::Origin]).unwrap()
+    }
+}
+
+impl Default for ZeroSizePolicy {
+    fn default() -> ZeroSizePolicy {
+        ZeroSizePolicy::Pack
+    }
+}
+
+/// Options controlling how rectangles are placed, independent of the free space they are packed
+/// into.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct PackerConfig<S> {
+    /// The heuristic used to choose between free rectangles
+    pub heuristic: Heuristic,
+    /// Whether rectangles may be rotated by 90 degrees when that gives a better placement.
+    /// Rotated rectangles are returned with their width and height swapped.
+    pub allow_rotation: bool,
+    /// The space kept free to the right of and below every packed rectangle, if any
+    pub padding: Option<S>,
+    /// The multiple that the padded width of every rectangle is rounded up to, if any, e.g. for
+    /// uploads that require rows of a certain alignment. Packed rectangles are still returned
+    /// with their actual width. Must be greater than zero.
+    pub row_pitch: Option<S>,
+    /// The multiples that the padded width and height of every rectangle are rounded up to, if
+    /// any. If the free rectangles also start at such multiples, every rectangle is placed at
+    /// one. Both must be greater than zero.
+    pub alignment: Option<Size<S>>,
+    /// A seed for breaking ties between equally good placements pseudo-randomly, or `None` to
+    /// prefer the lowest y and then the lowest x coordinate. Each seed always gives the same
+    /// packing, so different seeds can be tried to explore different packings.
+    pub tie_seed: Option<u64>,
+    /// How rectangles with a width or height of zero are packed
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub zero_size: ZeroSizePolicy,
+}
+
+impl<S> Default for PackerConfig<S> {
+    fn default() -> PackerConfig<S> {
+        PackerConfig{heuristic: Heuristic::Bssf, allow_rotation: false, padding: None, row_pitch: None, alignment: None,
+                     tie_seed: None, zero_size: ZeroSizePolicy::Pack}
+    }
+}
+
+impl<S> PackerConfig<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> {
+    /// The size a rectangle of size `size` takes up in the free rectangles, including padding,
+    /// row pitch and alignment
+    pub fn padded_size(&self, size: &Size<S>) -> Size<S> {
+        let mut padded = match self.padding {
+            Some(ref padding) => Size::new(size.width.clone() + padding.clone(),
+                                           size.height.clone() + padding.clone()),
+            None => size.clone(),
+        };
+
+        if let Some(ref pitch) = self.row_pitch {
+            padded.width = round_up(padded.width, pitch);
+        }
+
+        match self.alignment {
+            Some(ref alignment) => Size::new(round_up(padded.width, &alignment.width),
+                                             round_up(padded.height, &alignment.height)),
+            None => padded,
+        }
+    }
+}
+
+/// Generates options with positive row pitches and alignments, and non-negative padding.
+#[cfg(feature = "arbitrary")]
+impl<'a, S> ::arbitrary::Arbitrary<'a> for PackerConfig<S> where S: ::arbitrary::Arbitrary<'a> + PartialOrd + Default {
+    fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<PackerConfig<S>> {
+        use arbitrary::Arbitrary;
+
+        let padding: Option<S> = Arbitrary::arbitrary(u)?;
+        let row_pitch: Option<S> = Arbitrary::arbitrary(u)?;
+        let alignment: Option<Size<S>> = Arbitrary::arbitrary(u)?;
+        Ok(PackerConfig{
+            heuristic: Arbitrary::arbitrary(u)?,
+            allow_rotation: Arbitrary::arbitrary(u)?,
+            padding: padding.filter(|x| *x >= S::default()),
+            row_pitch: row_pitch.filter(|x| *x > S::default()),
+            alignment: alignment.filter(|x| x.width > S::default() && x.height > S::default()),
+            tie_seed: Arbitrary::arbitrary(u)?,
+            zero_size: Arbitrary::arbitrary(u)?,
+        })
+    }
+}
+
+/// Generates options with positive row pitches and alignments, and non-negative padding.
+#[cfg(feature = "quickcheck")]
+impl<S> ::quickcheck::Arbitrary for PackerConfig<S> where S: ::quickcheck::Arbitrary + PartialOrd + Default {
+    fn arbitrary(g: &mut ::quickcheck::Gen) -> PackerConfig<S> {
+        use quickcheck::Arbitrary;
+
+        PackerConfig{
+            heuristic: Arbitrary::arbitrary(g),
+            allow_rotation: Arbitrary::arbitrary(g),
+            padding: Option::<S>::arbitrary(g).filter(|x| *x >= S::default()),
+            row_pitch: Option::<S>::arbitrary(g).filter(|x| *x > S::default()),
+            alignment: Option::<Size<S>>::arbitrary(g).filter(|x| x.width > S::default() && x.height > S::default()),
+            tie_seed: Arbitrary::arbitrary(g),
+            zero_size: Arbitrary::arbitrary(g),
+        }
+    }
+}
+
+impl<S> PackerConfig<S> {
+    /// The default options with every rectangle aligned to blocks of `width` by `height`, e.g.
+    /// 4 by 4 for BC and ETC compressed textures. As long as the free rectangles start on block
+    /// boundaries, every packed rectangle covers whole blocks of its own, so that it can be
+    /// compressed on its own.
+    pub fn block_compressed(width: S, height: S) -> PackerConfig<S> {
+        PackerConfig{alignment: Some(Size::new(width, height)), ..PackerConfig::default()}
+    }
+}
+
+impl<S> PackerConfig<S> where S: From<u16> {
+    /// The default options with padding for an atlas with `levels` mip levels below the full
+    /// resolution, such that packed rectangles are at least a texel apart at the smallest level.
+    /// The padding doubles with every level. If `aligned`, rectangles are also aligned to the
+    /// padding, so that no texel at any level covers more than one of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `levels` is more than 15
+    pub fn mipmapped(levels: u32, aligned: bool) -> PackerConfig<S> {
+        assert!(levels <= 15, "at most 15 mip levels are supported");
+        let texels = 1u16 << levels;
+
+        PackerConfig{
+            padding: if levels > 0 { Some(texels.into()) } else { None },
+            alignment: if aligned { Some(Size::new(texels.into(), texels.into())) } else { None },
+            ..PackerConfig::default()
+        }
+    }
+}
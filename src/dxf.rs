@@ -0,0 +1,74 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use rect::Rect;
+
+/// Writes a drawing of a packed layout in the DXF format, e.g. to hand a cutting layout to CAM
+/// software. The outline of every bin is drawn on the layer `BINS`, the outline of every piece
+/// on the layer `PIECES`, and the label of every piece centered in it on the layer `LABELS`.
+/// Line breaks in a label are written as spaces, as every value in the file takes one line.
+///
+/// Only the entities section is written, as understood by any software reading AutoCAD R12
+/// files. As the y axis of a drawing points up, y coordinates are negated, so that the layout is
+/// not mirrored.
+pub fn write_dxf<W,S,L>(out: &mut W, bins: &[Rect<S>], pieces: &[(L, Rect<S>)]) -> io::Result<()>
+    where W: Write, S: Clone + Into<f64>, L: Display
+{
+    writeln!(out, "0\nSECTION\n2\nENTITIES")?;
+    for bin in bins.iter() {
+        write_outline(out, "BINS", bin)?;
+    }
+
+    for &(ref label, ref rect) in pieces.iter() {
+        write_outline(out, "PIECES", rect)?;
+
+        let (min_x, min_y, max_x, max_y) = corners(rect);
+        let (x, y) = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+        let height = f64::min(max_x - min_x, max_y - min_y) / 5.0;
+        let label = label.to_string().replace("\r\n", " ").replace(|x| x == '\r' || x == '\n', " ");
+        writeln!(out, "0\nTEXT\n8\nLABELS\n10\n{}\n20\n{}\n30\n0\n40\n{}\n1\n{}\n72\n1\n73\n2\n11\n{}\n21\n{}\n31\n0",
+                 x, y, height, label, x, y)?;
+    }
+    writeln!(out, "0\nENDSEC\n0\nEOF")
+}
+
+/// The minimum and maximum x and y coordinates of a rectangle in a drawing
+fn corners<S>(rect: &Rect<S>) -> (f64, f64, f64, f64) where S: Clone + Into<f64> {
+    (rect.min.x.clone().into(), -rect.max.y.clone().into(), rect.max.x.clone().into(), -rect.min.y.clone().into())
+}
+
+/// Writes the outline of a rectangle as four lines on `layer`
+fn write_outline<W,S>(out: &mut W, layer: &str, rect: &Rect<S>) -> io::Result<()> where W: Write, S: Clone + Into<f64> {
+    let (min_x, min_y, max_x, max_y) = corners(rect);
+    let points = [(min_x, min_y), (max_x, min_y), (max_x, max_y), (min_x, max_y)];
+    for index in 0..4 {
+        let ((x1, y1), (x2, y2)) = (points[index], points[(index + 1) % 4]);
+        writeln!(out, "0\nLINE\n8\n{}\n10\n{}\n20\n{}\n30\n0\n11\n{}\n21\n{}\n31\n0", layer, x1, y1, x2, y2)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::write_dxf;
+    use rect::Rect;
+
+    #[test]
+    fn dxf_layout() {
+        let mut out = Vec::new();
+        write_dxf(&mut out, &[Rect::new((0,0), (10,10))], &[("shelf", Rect::new((0,0), (10,5)))]).unwrap();
+        let dxf = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = dxf.lines().collect();
+
+        assert_eq!(lines[..4], ["0", "SECTION", "2", "ENTITIES"]);
+        assert_eq!(lines.iter().filter(|&&x| x == "LINE").count(), 8);
+        assert_eq!(lines.iter().filter(|&&x| x == "PIECES").count(), 4);
+        assert!(dxf.contains("\n1\nshelf\n") && dxf.contains("\n10\n5\n20\n-2.5\n"));
+        assert!(dxf.ends_with("0\nENDSEC\n0\nEOF\n"));
+
+        let mut out = Vec::new();
+        write_dxf(&mut out, &[], &[("top\nshelf\r\n", Rect::new((0,0), (10,5)))]).unwrap();
+        let dxf = String::from_utf8(out).unwrap();
+        assert!(dxf.contains("\n1\ntop shelf \n72\n"));
+    }
+}
@@ -0,0 +1,215 @@
+use std::cmp::Ordering;
+use std::ops::{Add, Sub, Mul};
+
+use arrayvec::ArrayVec;
+
+use {PackerConfig, MinMaxIteratorExt};
+use rect::Rect;
+use size::Size;
+use point::Point;
+
+/// A packer that never allocates, keeping at most `N` free rectangles in fixed-capacity storage,
+/// e.g. for packing widgets or glyphs on embedded targets without a heap.
+///
+/// It places rectangles as `RectPacker::pack` does, searching the free rectangles the same way.
+/// Whenever more than `N` free rectangles would be needed, the smallest by area are dropped, as
+/// with `RectPacker::set_max_free`, so that space is lost rather than memory allocated.
+#[derive(Clone, Debug)]
+pub struct FixedRectPacker<S, const N: usize> {
+    free: ArrayVec<Rect<S>, N>,
+    bounds: Option<Rect<S>>,
+    config: PackerConfig<S>,
+    placements: u64,
+}
+
+impl<S, const N: usize> FixedRectPacker<S, N> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> {
+    /// Creates a packer with no free rectangles and the default options
+    pub fn new() -> FixedRectPacker<S, N> {
+        FixedRectPacker::with_config(PackerConfig::default())
+    }
+
+    /// Creates a packer with no free rectangles that places rectangles according to `config`
+    pub fn with_config(config: PackerConfig<S>) -> FixedRectPacker<S, N> {
+        FixedRectPacker{free: ArrayVec::new(), bounds: None, config: config, placements: 0}
+    }
+
+    /// The free rectangles that can currently be packed into, none of which contains another
+    #[inline]
+    pub fn free_rects(&self) -> &[Rect<S>] {
+        &self.free
+    }
+
+    /// The bounding box of all free rectangles added, as `RectPacker::bounds`
+    #[inline]
+    pub fn bounds(&self) -> Option<&Rect<S>> {
+        self.bounds.as_ref()
+    }
+
+    /// The options rectangles are placed according to
+    #[inline]
+    pub fn config(&self) -> &PackerConfig<S> {
+        &self.config
+    }
+
+    /// Adds a free rectangle as `RectPacker::add_free` does, dropping the smallest free rectangle
+    /// if there are then more than `N`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either x or y in `min` is more than `max`
+    pub fn add_free<A, B>(&mut self, min: A, max: B) where A: Into<Point<S>>, B: Into<Point<S>> {
+        let rect = Rect::new(min, max);
+
+        if rect.min.x > rect.max.x {
+            panic!("min.x cannot be more than max.x");
+        }
+
+        if rect.min.y > rect.max.y {
+            panic!("min.y cannot be more than max.y");
+        }
+
+        self.bounds = Some(match self.bounds {
+            Some(ref bounds) => bounds.union(&rect),
+            None => rect.clone(),
+        });
+        self.insert_free(rect);
+    }
+
+    /// Packs a rectangle as `RectPacker::pack` does, including its zero-size policy, and returns
+    /// it, or returns `None` if it fits in no free rectangle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width or height is negative
+    pub fn pack(&mut self, width: S, height: S) -> Option<Rect<S>> {
+        let size = Size::new(width, height);
+        if let Some(placed) = self.config.admit(self.bounds.as_ref(), &size) {
+            return placed;
+        }
+
+        self.config.best_fit(self.placements, &self.free, &size).map(|candidate| {
+            let padded = self.config.padded_size(&candidate.size);
+            self.subtract_rect(&Rect::with_size(candidate.position.clone(), padded));
+            self.placements += 1;
+            Rect::with_size(candidate.position, candidate.size)
+        })
+    }
+
+    /// Adds a free rectangle unless a free rectangle contains it, removing the free rectangles
+    /// it contains. If all `N` are taken, the smallest of them and the new one is dropped.
+    fn insert_free(&mut self, rect: Rect<S>) {
+        if self.free.iter().any(|x| x.supersets(&rect)) {
+            return;
+        }
+        self.free.retain(|x| !rect.supersets(x));
+
+        if self.free.is_full() {
+            let compare = |a: &S, b: &S| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+            let smallest = self.free.iter()
+                .enumerate()
+                .min_cmp(|&(_, a), &(_, b)| compare(&a.area(), &b.area()))
+                .map(|(index, x)| (index, x.area()));
+
+            match smallest {
+                Some((index, ref area)) if compare(area, &rect.area()) == Ordering::Less => {
+                    trace!("dropped free rectangle over the capacity of {}", N);
+                    self.free.swap_remove(index);
+                }
+                _ => return,
+            }
+        }
+        self.free.push(rect);
+    }
+
+    /// Removes a rectangle from the free rectangles, splitting every free rectangle it intersects
+    /// into the up to four maximal rectangles around it.
+    fn subtract_rect(&mut self, sub: &Rect<S>) {
+        // The free rectangles are scanned from the back. The parts are disjoint from `sub`, and
+        // inserting them can only remove free rectangles that do not intersect `sub` either, so
+        // the rectangles before `index` that are still to be scanned stay before it.
+        let mut index = self.free.len();
+        while index > 0 {
+            index -= 1;
+            if index >= self.free.len() || !self.free[index].intersects(sub) {
+                continue;
+            }
+
+            let free = self.free.swap_remove(index);
+            let mut parts: ArrayVec<Rect<S>, 4> = ArrayVec::new();
+            if sub.min.x > free.min.x {
+                parts.push(Rect::new(free.min.clone(), Point::new(sub.min.x.clone(), free.max.y.clone())));
+            }
+            if sub.min.y > free.min.y {
+                parts.push(Rect::new(free.min.clone(), Point::new(free.max.x.clone(), sub.min.y.clone())));
+            }
+            if sub.max.x < free.max.x {
+                parts.push(Rect::new(Point::new(sub.max.x.clone(), free.min.y.clone()), free.max.clone()));
+            }
+            if sub.max.y < free.max.y {
+                parts.push(Rect::new(Point::new(free.min.x.clone(), sub.max.y.clone()), free.max.clone()));
+            }
+
+            for part in parts.into_iter() {
+                self.insert_free(part);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FixedRectPacker;
+    use {RectPacker, PackerConfig, ZeroSizePolicy};
+    use rect::Rect;
+
+    #[test]
+    fn fixed_pack() {
+        let mut packer: FixedRectPacker<u32, 8> = FixedRectPacker::new();
+        packer.add_free((0,0), (10,10));
+
+        assert_eq!(packer.pack(5,5), Some(Rect::new((0,0), (5,5))));
+        assert_eq!(packer.free_rects().len(), 2);
+        let packed: Vec<_> = (0..3).map(|_| packer.pack(5,5).unwrap()).collect();
+        for (i, a) in packed.iter().enumerate() {
+            assert!(packed[i + 1..].iter().all(|b| !a.intersects(b) && *b != Rect::new((0,0), (5,5))));
+        }
+        assert!(packer.free_rects().is_empty());
+        assert_eq!(packer.pack(1,1), None);
+
+        let mut packer: FixedRectPacker<u32, 1> = FixedRectPacker::new();
+        packer.add_free((0,0), (10,10));
+        packer.pack(5,4);
+        assert_eq!(packer.free_rects(), &[Rect::new((0,4), (10,10))][..]);
+        assert_eq!(packer.pack(6,7), None);
+    }
+
+    #[test]
+    fn fixed_pack_like_packer() {
+        let sizes = [(3,7), (5,2), (0,4), (6,6), (2,9), (4,0), (7,3)];
+        for &policy in [ZeroSizePolicy::Pack, ZeroSizePolicy::Reject, ZeroSizePolicy::Skip, ZeroSizePolicy::Origin].iter() {
+            let config = PackerConfig{zero_size: policy, allow_rotation: true, ..PackerConfig::default()};
+            let mut fixed: FixedRectPacker<u32, 64> = FixedRectPacker::with_config(config.clone());
+            let mut packer = RectPacker::builder().config(config).build();
+            fixed.add_free((0,0), (16,16));
+            packer.add_free((0,0), (16,16));
+
+            for &(width, height) in sizes.iter() {
+                assert_eq!(fixed.pack(width, height), packer.pack(width, height));
+            }
+            let sorted = |free: &[Rect<u32>]| {
+                let mut free = free.to_vec();
+                free.sort_by_key(|x| (x.min.x, x.min.y, x.max.x, x.max.y));
+                free
+            };
+            assert_eq!(sorted(fixed.free_rects()), sorted(packer.free_rects()));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fixed_negative_pack() {
+        let mut packer: FixedRectPacker<i32, 8> = FixedRectPacker::new();
+        packer.add_free((0,0), (10,10));
+        packer.pack(-5,5);
+    }
+}
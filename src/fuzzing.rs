@@ -0,0 +1,146 @@
+//! A harness for fuzzing the packer, e.g. with `cargo fuzz`:
+//!
+//! ```ignore
+//! fuzz_target!(|ops: Vec<maxrects::fuzzing::Op>| maxrects::fuzzing::run_ops(&ops));
+//! ```
+
+use {RectPacker, PackerConfig, Operation, Interrupt, Grain};
+use rect::Rect;
+use size::Size;
+
+/// An operation applied by `run_ops`. Coordinates are `u16`, and are widened to `u64` for the
+/// packer, so that no area or padded size can overflow.
+pub type Op = Operation<u16>;
+
+/// Stops a global packing after a number of placements, as `Operation::PackGlobal` records.
+struct After(usize);
+
+impl Interrupt for After {
+    fn interrupted(&mut self) -> bool {
+        match self.0.checked_sub(1) {
+            Some(left) => { self.0 = left; false }
+            None => true,
+        }
+    }
+}
+
+/// Applies operations to a new packer, checking its invariants after every operation:
+///
+/// * The free rectangles lie within the bounds, and none contains another.
+/// * The packed rectangles lie within the bounds, and do not intersect each other or any free
+///   rectangle.
+///
+/// A packed rectangle is considered freed, and no longer checked, once a free rectangle
+/// intersecting it is added.
+///
+/// # Panics
+///
+/// Panics if an invariant does not hold, or if the packer panics
+pub fn run_ops(ops: &[Op]) {
+    let mut packer: RectPacker<u64> = RectPacker::new();
+    let mut packed: Vec<Rect<u64>> = Vec::new();
+
+    for op in ops.iter() {
+        match *op {
+            Operation::AddFree(ref rect) => {
+                let rect = widen_rect(rect);
+                packed.retain(|x| !x.intersects(&rect));
+                packer.add_free(rect.min, rect.max);
+            }
+            Operation::GrowTo(ref max) => packer.grow_to((max.x.into(), max.y.into())),
+            Operation::Pack(ref size) => {
+                packed.extend(packer.pack(size.width.into(), size.height.into()));
+            }
+            Operation::PackNear(ref size, ref near) => {
+                packed.extend(packer.pack_near(size.width.into(), size.height.into(), &widen_rect(near)));
+            }
+            Operation::PackGlobal(ref sizes, placements) => {
+                let sizes: Vec<Size<u64>> = sizes.iter().map(widen_size).collect();
+                match packer.pack_global_interruptible(sizes, |x| x.clone(), After(placements)) {
+                    Ok(placed) => packed.extend(placed.into_iter().map(|(_, rect)| rect)),
+                    Err(failed) => packed.extend(failed.packed().iter().map(|&(_, rect)| rect)),
+                }
+            }
+            Operation::PackGlobalWithGrain(ref items, _) => {
+                // Packing with grain cannot be interrupted, so every object is placed that fits
+                let items: Vec<(Size<u64>, Grain)> = items.iter().map(|&(ref size, grain)| (widen_size(size), grain)).collect();
+                match packer.pack_global_with_grain(items, |x| x.0.clone(), |x| x.1) {
+                    Ok(placed) => packed.extend(placed.into_iter().map(|(_, rect)| rect)),
+                    Err(failed) => packed.extend(failed.packed().iter().map(|&(_, rect)| rect)),
+                }
+            }
+            Operation::SetMaxFree(limit) => packer.set_max_free(limit),
+            Operation::SetConfig(ref config) => packer.set_config(PackerConfig{
+                heuristic: config.heuristic,
+                allow_rotation: config.allow_rotation,
+                padding: config.padding.map(u64::from),
+                row_pitch: config.row_pitch.map(u64::from),
+                alignment: config.alignment.as_ref().map(widen_size),
+                tie_seed: config.tie_seed,
+                zero_size: config.zero_size,
+            }),
+        }
+
+        check(&packer, &packed, op);
+    }
+}
+
+/// Panics if the invariants listed in `run_ops` do not hold after `op`
+fn check(packer: &RectPacker<u64>, packed: &[Rect<u64>], op: &Op) {
+    let free = packer.free_rects();
+    let within = |rect: &Rect<u64>| packer.bounds().map_or(false, |bounds| bounds.supersets(rect));
+
+    for (index, rect) in free.iter().enumerate() {
+        assert!(within(rect), "free {:?} is out of bounds after {:?}", rect, op);
+        assert!(free.iter().enumerate().all(|(other, x)| other == index || !x.supersets(rect)),
+                "free {:?} is contained in another after {:?}", rect, op);
+    }
+
+    for (index, rect) in packed.iter().enumerate() {
+        assert!(within(rect), "packed {:?} is out of bounds after {:?}", rect, op);
+        assert!(packed[index + 1..].iter().all(|x| !x.intersects(rect)),
+                "packed {:?} intersects another after {:?}", rect, op);
+        assert!(free.iter().all(|x| !x.intersects(rect)),
+                "packed {:?} intersects a free rectangle after {:?}", rect, op);
+    }
+}
+
+fn widen_size(size: &Size<u16>) -> Size<u64> {
+    Size::new(size.width.into(), size.height.into())
+}
+
+fn widen_rect(rect: &Rect<u16>) -> Rect<u64> {
+    Rect::new((rect.min.x.into(), rect.min.y.into()), (rect.max.x.into(), rect.max.y.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use super::{Op, run_ops};
+    use {Operation, Grain};
+    use rect::Rect;
+    use size::Size;
+    use point::Point;
+
+    #[test]
+    fn fuzzing_ops() {
+        run_ops(&[
+            Operation::AddFree(Rect::new((0,0), (10,10))),
+            Operation::Pack(Size::new(5,5)),
+            Operation::PackGlobal(vec![Size::new(5,5), Size::new(5,5), Size::new(6,6)], 1),
+            Operation::AddFree(Rect::new((0,0), (5,5))),
+            Operation::SetMaxFree(Some(1)),
+            Operation::PackNear(Size::new(2,2), Rect::new((9,9), (10,10))),
+            Operation::GrowTo(Point::new(12,10)),
+            Operation::PackGlobalWithGrain(vec![(Size::new(1,3), Grain::Height), (Size::new(2,1), Grain::Any)], 2),
+        ]);
+
+        for seed in 0..32u32 {
+            let bytes: Vec<u8> = (0..256u32).map(|x| (x.wrapping_mul(seed * 2 + 1) >> 3) as u8).collect();
+            let mut u = Unstructured::new(&bytes);
+            let ops: Vec<Op> = (0..16).filter_map(|_| Op::arbitrary(&mut u).ok()).collect();
+            run_ops(&ops);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Seeded generators of synthetic workloads for benchmarking and property testing. The same seed
+//! always generates the same workload.
+
+use instances::Instance;
+use size::Size;
+use mix;
+
+/// A generator of rectangle sizes and instances from a seed.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct InstanceGenerator {
+    state: u64,
+}
+
+impl InstanceGenerator {
+    /// Creates a generator from a seed
+    pub fn new(seed: u64) -> InstanceGenerator {
+        InstanceGenerator{state: seed}
+    }
+
+    /// A pseudo-random number
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(1);
+        mix(self.state)
+    }
+
+    /// A pseudo-random number in `min..=max`
+    fn between(&mut self, min: u32, max: u32) -> u32 {
+        min + (self.next() % (max as u64 - min as u64 + 1)) as u32
+    }
+
+    /// A pseudo-random number in `0..1`
+    fn fraction(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Generates `count` sizes with widths and heights distributed uniformly between those of
+    /// `min` and `max`, inclusive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is wider or taller than `max`
+    pub fn uniform(&mut self, count: usize, min: Size<u32>, max: Size<u32>) -> Vec<Size<u32>> {
+        assert!(min.width <= max.width && min.height <= max.height, "min cannot be more than max");
+        (0..count).map(|_| {
+            let width = self.between(min.width, max.width);
+            Size::new(width, self.between(min.height, max.height))
+        }).collect()
+    }
+
+    /// Generates `count` sizes drawn from `sizes` with Zipfian frequencies, as with the sprites
+    /// of a game, where a few sizes are very common. The size at index `k` is drawn with a
+    /// frequency proportional to `1 / (k + 1)^exponent`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sizes` is empty
+    pub fn zipf(&mut self, count: usize, sizes: &[Size<u32>], exponent: f64) -> Vec<Size<u32>> {
+        assert!(!sizes.is_empty(), "there must be sizes to draw from");
+        let weights: Vec<f64> = (0..sizes.len()).map(|k| 1.0 / ((k + 1) as f64).powf(exponent)).collect();
+        let total: f64 = weights.iter().sum();
+
+        (0..count).map(|_| {
+            let mut target = self.fraction() * total;
+            let index = weights.iter().position(|weight| {
+                target -= *weight;
+                target < 0.0
+            });
+            sizes[index.unwrap_or(sizes.len() - 1)].clone()
+        }).collect()
+    }
+
+    /// Generates an instance whose rectangles exactly tile a single bin of size `bin`, so that
+    /// the optimal packing is known to use one bin fully. The bin is cut in two by guillotine
+    /// cuts, each across a random piece at a random position, until there are `count` pieces or
+    /// no piece can be cut. The pieces are returned in random order. There is always at least one
+    /// piece, the uncut bin, even if `count` is 0.
+    pub fn guillotine(&mut self, bin: Size<u32>, count: usize) -> Instance {
+        let mut pieces = vec![bin.clone()];
+        while pieces.len() < count {
+            let cuttable: Vec<usize> = (0..pieces.len()).filter(|&x| pieces[x].width > 1 || pieces[x].height > 1).collect();
+            if cuttable.is_empty() {
+                break;
+            }
+
+            let index = cuttable[self.between(0, cuttable.len() as u32 - 1) as usize];
+            let piece = pieces[index].clone();
+            let vertical = piece.height < 2 || (piece.width > 1 && self.next() % 2 == 0);
+            if vertical {
+                let cut = self.between(1, piece.width - 1);
+                pieces[index] = Size::new(cut, piece.height);
+                pieces.push(Size::new(piece.width - cut, piece.height));
+            } else {
+                let cut = self.between(1, piece.height - 1);
+                pieces[index] = Size::new(piece.width, cut);
+                pieces.push(Size::new(piece.width, piece.height - cut));
+            }
+        }
+
+        for index in (1..pieces.len()).rev() {
+            let other = self.between(0, index as u32) as usize;
+            pieces.swap(index, other);
+        }
+        Instance{bin: bin, items: pieces}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::InstanceGenerator;
+    use size::Size;
+
+    #[test]
+    fn generated_instances() {
+        let mut generator = InstanceGenerator::new(7);
+        let sizes = generator.uniform(100, Size::new(1,2), Size::new(4,3));
+        assert!(sizes.iter().all(|x| x.width >= 1 && x.width <= 4 && x.height >= 2 && x.height <= 3));
+        assert_eq!(sizes, InstanceGenerator::new(7).uniform(100, Size::new(1,2), Size::new(4,3)));
+
+        let palette = [Size::new(16,16), Size::new(32,32), Size::new(8,64)];
+        let sprites = generator.zipf(1000, &palette, 1.5);
+        let counts: Vec<_> = palette.iter().map(|x| sprites.iter().filter(|y| *y == x).count()).collect();
+        assert!(counts[0] > counts[1] && counts[1] > counts[2] && counts[2] > 0);
+
+        let instance = generator.guillotine(Size::new(64,32), 20);
+        assert_eq!(instance.items.len(), 20);
+        assert_eq!(instance.item_area(), 64 * 32);
+        assert_eq!(generator.guillotine(Size::new(2,1), 20).items, vec![Size::new(1,1); 2]);
+        assert_eq!(generator.guillotine(Size::new(2,1), 0).items, vec![Size::new(2,1)]);
+    }
+}
@@ -0,0 +1,87 @@
+use ab_glyph::OutlinedGlyph;
+
+use {RectPacker, FailedPacking};
+use rect::Rect;
+use size::{Size, SizeSource};
+
+/// A glyph packed into a font atlas by `RectPacker::pack_glyphs`.
+#[derive(Clone, Debug)]
+pub struct PackedGlyph {
+    /// The outlined glyph, with its id, scale, position and pixel bounds
+    pub glyph: OutlinedGlyph,
+    /// The rectangle of the atlas holding the rasterized glyph
+    pub rect: Rect<u32>,
+}
+
+impl PackedGlyph {
+    /// Whether the glyph was rotated to be packed, in which case it is stored transposed, with
+    /// its rows as columns of `rect`
+    pub fn rotated(&self) -> bool {
+        self.rect.width() != self.glyph.rect_size().width
+    }
+
+    /// Rasterizes the glyph, calling `pixel` with the atlas coordinates and coverage of every
+    /// pixel of its rectangle
+    pub fn draw<F>(&self, mut pixel: F) where F: FnMut(u32, u32, f32) {
+        let (min, rotated) = (self.rect.min, self.rotated());
+        self.glyph.draw(|x, y, coverage| if rotated {
+            pixel(min.x + y, min.y + x, coverage)
+        } else {
+            pixel(min.x + x, min.y + y, coverage)
+        });
+    }
+}
+
+impl SizeSource<u32> for OutlinedGlyph {
+    fn rect_size(&self) -> Size<u32> {
+        let bounds = self.px_bounds();
+        Size::new(bounds.width() as u32, bounds.height() as u32)
+    }
+}
+
+impl RectPacker<u32> {
+    /// Globally packs the pixel bounds of outlined glyphs, as `pack_global_sized`, e.g. to build
+    /// a font atlas. Glyphs are kept apart by the padding of the packer.
+    pub fn pack_glyphs(&mut self, glyphs: Vec<OutlinedGlyph>)
+        -> Result<Vec<PackedGlyph>, FailedPacking<OutlinedGlyph,u32>>
+    {
+        self.pack_global_sized(glyphs).map(|packed| {
+            packed.into_iter().map(|(glyph, rect)| PackedGlyph{glyph: glyph, rect: rect}).collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ab_glyph::{Glyph, GlyphId, Outline, OutlineCurve, OutlinedGlyph, PxScaleFactor, point};
+
+    use RectPacker;
+    use rect::Rect;
+
+    fn block(width: f32, height: f32) -> OutlinedGlyph {
+        let corners = [point(0.0, 0.0), point(width, 0.0), point(width, height), point(0.0, height)];
+        let outline = Outline{
+            // Outlines are y-up, so their bounds start at the top
+            bounds: ::ab_glyph::Rect{min: corners[3], max: corners[1]},
+            curves: (0..4).map(|i| OutlineCurve::Line(corners[i], corners[(i + 1) % 4])).collect(),
+        };
+        let glyph = Glyph{id: GlyphId(0), scale: 1.0.into(), position: point(0.0, 0.0)};
+        OutlinedGlyph::new(glyph, outline, PxScaleFactor{horizontal: 1.0, vertical: 1.0})
+    }
+
+    #[test]
+    fn glyphs() {
+        let mut packer = RectPacker::builder().bin(0,0,16,16).allow_rotation(true).build();
+        let packed = packer.pack_glyphs(vec![block(16.0, 8.0), block(8.0, 16.0)]).unwrap();
+
+        let rects: Vec<_> = packed.iter().map(|x| (x.rect, x.rotated())).collect();
+        assert_eq!(rects, vec![(Rect::new((0,0), (16,8)), false), (Rect::new((0,8), (16,16)), true)]);
+
+        let mut covered = 0;
+        packed[1].draw(|x, y, coverage| {
+            assert!(packed[1].rect.contains(&(x, y).into()));
+            if coverage > 0.5 { covered += 1; }
+        });
+        assert_eq!(covered, 8 * 16);
+    }
+}
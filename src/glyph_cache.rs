@@ -0,0 +1,169 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use RectPacker;
+use allocator::{AtlasAllocator, AllocId};
+use rect::Rect;
+
+/// A reference to a rectangle allocated in a `GlyphCache`. It stays valid until the rectangle is
+/// evicted or removed, after which it is detected as stale, even if its space is reused.
+pub type GlyphHandle = AllocId;
+
+/// A cache of rectangles allocated at runtime, e.g. of the glyphs rendered by a text renderer.
+/// Rectangles are looked up by key and, when there is no room for a new one, the least recently
+/// used ones are evicted until there is.
+#[derive(Clone, Debug)]
+pub struct GlyphCache<K> {
+    allocator: AtlasAllocator,
+    /// The handle of every rectangle and the time it was last used
+    keys: HashMap<K, (GlyphHandle, u64)>,
+    /// The key of every rectangle by the time it was last used, least recently used first
+    lru: BTreeMap<u64, K>,
+    clock: u64,
+}
+
+impl<K> GlyphCache<K> where K: Clone + Hash + Eq {
+    /// Creates an empty cache allocating from the free rectangles of `packer`, with its options
+    pub fn new(packer: RectPacker<u32>) -> GlyphCache<K> {
+        GlyphCache{
+            allocator: AtlasAllocator::with_packer(packer),
+            keys: HashMap::new(),
+            lru: BTreeMap::new(),
+            clock: 0,
+        }
+    }
+
+    /// The number of rectangles allocated
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether no rectangles are allocated
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The packer allocating the rectangles
+    pub fn packer(&self) -> &RectPacker<u32> {
+        self.allocator.packer()
+    }
+
+    /// Looks up the rectangle allocated for `key`, marking it as used
+    pub fn get(&mut self, key: &K) -> Option<GlyphHandle> {
+        self.clock += 1;
+        let clock = self.clock;
+
+        let lru = &mut self.lru;
+        self.keys.get_mut(key).map(|entry| {
+            let key = lru.remove(&entry.1).unwrap();
+            lru.insert(clock, key);
+            entry.1 = clock;
+            entry.0
+        })
+    }
+
+    /// Looks up the rectangle allocated for `key` as `get` does, or allocates one of the given size
+    /// for it, evicting the least recently used rectangles as necessary. Returns `None` if the
+    /// rectangle does not fit even with every other rectangle evicted.
+    pub fn insert(&mut self, key: K, width: u32, height: u32) -> Option<GlyphHandle> {
+        if let Some(handle) = self.get(&key) {
+            return Some(handle);
+        }
+
+        if !self.allocator.fits_empty((width, height)) {
+            return None;
+        }
+
+        let area = width as u64 * height as u64;
+        let handle = loop {
+            if let Some(allocation) = self.allocator.allocate((width, height)) {
+                break allocation.id;
+            } else if self.is_empty() {
+                return None;
+            }
+
+            // The rectangle cannot fit before at least its area is free, so every rectangle
+            // evicted until then would have to be evicted anyway
+            let mut free = self.allocator.free_space();
+            let mut evicted = Vec::new();
+            for (_, key) in self.lru.iter() {
+                if free >= area && !evicted.is_empty() {
+                    break;
+                }
+                let rect = self.allocator.get(self.keys[key].0).unwrap();
+                free += rect.width() as u64 * rect.height() as u64;
+                evicted.push(key.clone());
+            }
+            debug!("evicting {} glyphs to make room for {}x{}", evicted.len(), width, height);
+            for key in evicted.iter() {
+                self.remove(key);
+            }
+        };
+
+        self.clock += 1;
+        self.lru.insert(self.clock, key.clone());
+        self.keys.insert(key, (handle, self.clock));
+        Some(handle)
+    }
+
+    /// The rectangle a handle refers to, or `None` if it has been evicted or removed since
+    pub fn rect(&self, handle: GlyphHandle) -> Option<Rect<u32>> {
+        self.allocator.get(handle)
+    }
+
+    /// Removes the rectangle allocated for `key`, freeing its space, and returns it
+    pub fn remove(&mut self, key: &K) -> Option<Rect<u32>> {
+        self.keys.remove(key).map(|(handle, last_used)| {
+            self.lru.remove(&last_used);
+            let rect = self.allocator.get(handle).unwrap();
+            self.allocator.deallocate(handle);
+            rect
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GlyphCache;
+    use RectPacker;
+    use rect::Rect;
+
+    #[test]
+    fn eviction() {
+        let mut cache = GlyphCache::new(RectPacker::builder().bin(0,0,20,10).build());
+        let a = cache.insert('a', 10, 10).unwrap();
+        let b = cache.insert('b', 10, 10).unwrap();
+        assert_eq!(cache.insert('a', 10, 10), Some(a));
+
+        let c = cache.insert('c', 10, 10).unwrap();
+        assert_eq!(cache.rect(b), None);
+        assert_eq!(cache.rect(c), Some(Rect::new((10,0), (20,10))));
+        assert_eq!(cache.get(&'b'), None);
+        assert_eq!(cache.len(), 2);
+
+        let wide = cache.insert('w', 20, 10).unwrap();
+        assert_eq!((cache.rect(a), cache.rect(c)), (None, None));
+        assert_eq!(cache.rect(wide), Some(Rect::new((0,0), (20,10))));
+        assert_eq!(cache.insert('x', 30, 10), None);
+        assert_eq!(cache.rect(wide), Some(Rect::new((0,0), (20,10))));
+
+        assert_eq!(cache.remove(&'w'), Some(Rect::new((0,0), (20,10))));
+        assert!(cache.is_empty() && cache.rect(wide).is_none());
+        assert_eq!(cache.packer().free_rects(), &[Rect::new((0,0), (20,10))]);
+    }
+
+    #[test]
+    fn batched_eviction() {
+        let mut cache = GlyphCache::new(RectPacker::builder().bin(0,0,20,20).build());
+        let small: Vec<_> = (0..16).map(|x| cache.insert(x, 5, 5).unwrap()).collect();
+        cache.get(&0);
+
+        // At least the eight least recently used rectangles have to go before there is room, and
+        // then more until the rectangles left in the way are gone
+        let half = cache.insert(16, 20, 10).unwrap();
+        assert_eq!(cache.rect(half), Some(Rect::new((0,5), (20,15))));
+        assert_eq!(cache.len(), 4);
+        assert!(small[1..14].iter().all(|&x| cache.rect(x).is_none()));
+        assert!(cache.get(&0).is_some() && cache.get(&14).is_some() && cache.get(&15).is_some());
+    }
+}
@@ -0,0 +1,104 @@
+use std::ops::{Add, Sub, Mul};
+
+use {RectPacker, FailedPacking, PackedResult, Operation, MaybeDebug, MaybeSync};
+use rect::Rect;
+use size::Size;
+
+/// The direction of the grain of a piece cut from sheet material such as plywood or fabric,
+/// whose grain is taken to run along the x axis of the bins. A piece with a grain direction is
+/// only ever placed with its grain along that of the sheet, even when rotation is allowed.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub enum Grain {
+    /// The piece has no grain, and may be rotated if rotation is allowed
+    Any,
+    /// The grain runs along the width of the piece, which is never rotated
+    Width,
+    /// The grain runs along the height of the piece, which is always rotated
+    Height,
+}
+
+#[cfg(feature = "quickcheck")]
+impl ::quickcheck::Arbitrary for Grain {
+    fn arbitrary(g: &mut ::quickcheck::Gen) -> Grain {
+        *g.choose(&[Grain::Any, Grain::Width, Grain::Height]).unwrap()
+    }
+}
+
+impl Default for Grain {
+    fn default() -> Grain {
+        Grain::Any
+    }
+}
+
+impl Grain {
+    /// The size a piece of size `size` is placed with, and whether it may still be rotated
+    pub fn orient<S>(&self, size: Size<S>) -> (Size<S>, bool) {
+        match *self {
+            Grain::Any => (size, true),
+            Grain::Width => (size, false),
+            Grain::Height => (Size::new(size.height, size.width), false),
+        }
+    }
+}
+
+impl<S> RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug + MaybeSync {
+    /// Packs a rectangle as `pack` does, but with its grain along that of the bins. As with
+    /// rotation, the packed rectangle has its width and height swapped if its grain runs along
+    /// its height.
+    pub fn pack_with_grain(&mut self, width: S, height: S, grain: Grain) -> Option<Rect<S>> {
+        let size = Size::new(width, height);
+        self.pack_global_with_grain(vec![()], |_| size.clone(), |_| grain).ok()
+            .and_then(|packed| packed.into_vec().pop())
+            .map(|(_, rect)| rect)
+    }
+
+    /// Globally packs objects as `pack_global`, but with the grain of every object, as given by
+    /// `grain`, along that of the bins. Objects without grain may still be rotated if rotation is
+    /// allowed.
+    pub fn pack_global_with_grain<T,F,Z,G>(&mut self, objects: Vec<T>, mut mapping: F, mut grain: G)
+        -> Result<PackedResult<T,S>, FailedPacking<T,S>>
+        where F: for<'a>FnMut(&'a T) -> Z, Z: Into<Size<S>>, G: for<'a>FnMut(&'a T) -> Grain
+    {
+        let items: Vec<(Size<S>, Grain)> = objects.iter().map(|x| (mapping(x).into(), grain(x))).collect();
+        let sizes = items.iter().map(|&(ref size, grain)| grain.orient(size.clone())).collect();
+
+        let result = self.pack_global_sizes(objects, sizes, |_| (), || false);
+        let placements = match result {
+            Ok(ref packed) => packed.len(),
+            Err(ref failed) => failed.packed().len(),
+        };
+        self.record(Operation::PackGlobalWithGrain(items, placements));
+        result.map(|packed| PackedResult::new(packed, self.bounds.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Grain;
+    use RectPacker;
+    use rect::Rect;
+
+    #[test]
+    fn grain_pack() {
+        let mut packer = RectPacker::builder().bin(0,0,10,4).allow_rotation(true).build();
+        assert_eq!(packer.pack_with_grain(4,10, Grain::Width), None);
+        assert_eq!(packer.pack_with_grain(4,10, Grain::Height), Some(Rect::new((0,0), (10,4))));
+
+        let mut packer = RectPacker::builder().bin(0,0,10,10).allow_rotation(true).build();
+        let pieces = vec![(6,6, Grain::Any), (4,10, Grain::Height), (2,2, Grain::Width)];
+        let packed = packer.pack_global_with_grain(pieces, |&(w, h, _)| (w, h), |&(_, _, grain)| grain).unwrap();
+        assert!(packed.iter().any(|&(piece, rect)| piece.2 == Grain::Height && (rect.width(), rect.height()) == (10,4)));
+        assert!(packed.iter().any(|&(piece, rect)| piece.2 == Grain::Width && (rect.width(), rect.height()) == (2,2)));
+
+        let mut recorded = RectPacker::builder().bin(0,0,10,10).allow_rotation(true).build();
+        recorded.start_recording();
+        recorded.pack_with_grain(3,7, Grain::Width);
+        let operations = recorded.stop_recording().unwrap();
+
+        let mut replayed = RectPacker::new();
+        replayed.replay(&operations);
+        assert_eq!(replayed.free_rects(), recorded.free_rects());
+    }
+}
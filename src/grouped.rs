@@ -0,0 +1,117 @@
+use std::collections::{BTreeMap, HashMap};
+use std::cmp::Ordering;
+use std::hash::Hash;
+use std::ops::{Add, Sub, Mul};
+
+use {RectPacker, MaybeDebug, MaybeSync, sum};
+use rect::Rect;
+use size::Size;
+
+/// Rectangles to pack into a number of bins at once, each with an id and optionally a group.
+/// All rectangles of a group are packed into the same bin.
+#[derive(Clone, Debug)]
+pub struct GroupedRects<I, G, S> {
+    rects: Vec<(I, Option<G>, Size<S>)>,
+}
+
+impl<I, G, S> GroupedRects<I, G, S>
+    where I: Clone + Hash + Eq, G: Hash + Eq,
+          S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug + MaybeSync
+{
+    /// Creates an empty set of rectangles
+    pub fn new() -> GroupedRects<I, G, S> {
+        GroupedRects{rects: Vec::new()}
+    }
+
+    /// Adds a rectangle of the given size with an id and, if it must share a bin with other
+    /// rectangles, their group
+    pub fn push<Z>(&mut self, id: I, group: Option<G>, size: Z) where Z: Into<Size<S>> {
+        self.rects.push((id, group, size.into()));
+    }
+
+    /// Packs every rectangle into one of `bins`, and returns the bin and rectangle of every id.
+    ///
+    /// Groups and ungrouped rectangles are packed largest first by total area, each globally into
+    /// the first bin, in the order of their keys, that all of their rectangles fit in. If some do
+    /// not fit in any bin, their ids are returned and `bins` are left unchanged.
+    pub fn pack<B>(&self, bins: &mut BTreeMap<B, RectPacker<S>>) -> Result<HashMap<I, (B, Rect<S>)>, Vec<I>>
+        where B: Clone + Ord
+    {
+        let mut units: Vec<Vec<usize>> = Vec::new();
+        let mut groups: HashMap<&G, usize> = HashMap::new();
+        for (index, &(_, ref group, _)) in self.rects.iter().enumerate() {
+            match group.as_ref().and_then(|x| groups.get(x).cloned()) {
+                Some(unit) => units[unit].push(index),
+                None => {
+                    if let Some(ref group) = *group {
+                        groups.insert(group, units.len());
+                    }
+                    units.push(vec![index]);
+                }
+            }
+        }
+
+        let area = |unit: &Vec<usize>| sum(unit.iter().map(|&x| {
+            let size = &self.rects[x].2;
+            size.width.clone() * size.height.clone()
+        }));
+        units.sort_by(|a, b| area(b).partial_cmp(&area(a)).unwrap_or(Ordering::Equal));
+
+        let mut packed = bins.clone();
+        let mut locations = HashMap::new();
+        let mut unplaced = Vec::new();
+        for unit in units.into_iter() {
+            let placed = packed.iter_mut().filter_map(|(bin, packer)| {
+                let mut attempt = packer.clone();
+                attempt.pack_global(unit.clone(), |&x| self.rects[x].2.clone()).ok().map(|placed| {
+                    *packer = attempt;
+                    (bin.clone(), placed)
+                })
+            }).next();
+
+            match placed {
+                Some((bin, placed)) => for (index, rect) in placed.into_iter() {
+                    locations.insert(self.rects[index].0.clone(), (bin.clone(), rect));
+                },
+                None => unplaced.extend(unit.into_iter().map(|x| self.rects[x].0.clone())),
+            }
+        }
+
+        if unplaced.is_empty() {
+            *bins = packed;
+            Ok(locations)
+        } else {
+            debug!("{} grouped rectangles do not fit in any of {} bins", unplaced.len(), bins.len());
+            Err(unplaced)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use super::GroupedRects;
+    use RectPacker;
+    use rect::Rect;
+
+    #[test]
+    fn grouped_pack() {
+        let mut rects = GroupedRects::new();
+        rects.push(0, Some("a"), (10,5));
+        rects.push(1, Some("b"), (10,10));
+        rects.push(2, Some("a"), (10,5));
+        rects.push(3, None, (5,5));
+
+        let mut bins: BTreeMap<_, _> = (0..2).map(|x| (x, RectPacker::builder().bin(0,0,10,10).build())).collect();
+        assert_eq!(rects.pack(&mut bins), Err(vec![3]));
+        assert!(bins.values().all(|x| x.packed_area().is_none()));
+
+        bins.insert(2, RectPacker::builder().bin(0,0,5,5).build());
+        let locations = rects.pack(&mut bins).unwrap();
+        assert_eq!((locations[&0].0, locations[&2].0), (0, 0));
+        assert_eq!(locations[&1], (1, Rect::new((0,0), (10,10))));
+        assert_eq!(locations[&3].0, 2);
+        assert!(bins.values().all(|x| x.free_rects().is_empty()));
+    }
+}
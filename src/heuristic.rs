@@ -0,0 +1,73 @@
+use std::ops::{Add, Sub, Mul, Range};
+
+use rect::Rect;
+use size::Size;
+
+/// A heuristic for choosing which free rectangle to pack a rectangle into. Every possible
+/// placement is given a score, and the placement with the lowest score is chosen.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub enum Heuristic {
+    /// Best short side fit: minimizes the shorter leftover side of the free rectangle
+    Bssf,
+    /// Best long side fit: minimizes the longer leftover side of the free rectangle
+    Blsf,
+    /// Best area fit: minimizes the leftover area of the free rectangle
+    Baf,
+    /// Bottom-left: minimizes the maximum y coordinate of the packed rectangle
+    BottomLeft,
+}
+
+#[cfg(feature = "quickcheck")]
+impl ::quickcheck::Arbitrary for Heuristic {
+    fn arbitrary(g: &mut ::quickcheck::Gen) -> Heuristic {
+        *g.choose(&[Heuristic::Bssf, Heuristic::Blsf, Heuristic::Baf, Heuristic::BottomLeft]).unwrap()
+    }
+}
+
+impl Default for Heuristic {
+    fn default() -> Heuristic {
+        Heuristic::Bssf
+    }
+}
+
+impl Heuristic {
+    /// Scores packing a rectangle of size `size` into the minimum corner of `free`, or returns
+    /// `None` if it does not fit.
+    pub fn score<S>(&self, free: &Rect<S>, size: &Size<S>) -> Option<S>
+        where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S>
+    {
+        let (width, height) = (free.width(), free.height());
+        if width < size.width || height < size.height {
+            return None;
+        }
+
+        let leftover_x = width.clone() - size.width.clone();
+        let leftover_y = height.clone() - size.height.clone();
+
+        Some(match *self {
+            Heuristic::Bssf => if leftover_x < leftover_y { leftover_x } else { leftover_y },
+            Heuristic::Blsf => if leftover_x < leftover_y { leftover_y } else { leftover_x },
+            Heuristic::Baf => width * height - size.width.clone() * size.height.clone(),
+            Heuristic::BottomLeft => free.min.y.clone() + size.height.clone(),
+        })
+    }
+
+    /// Scores packing a range of length `length` into the start of `free`, as a rectangle of no
+    /// height, or returns `None` if it does not fit. Every fit heuristic then minimizes the
+    /// leftover length, while `BottomLeft` minimizes the end of the packed range.
+    pub fn score_range<S>(&self, free: &Range<S>, length: &S) -> Option<S>
+        where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S>
+    {
+        let leftover = free.end.clone() - free.start.clone();
+        if leftover < *length {
+            return None;
+        }
+
+        Some(match *self {
+            Heuristic::Bssf | Heuristic::Blsf | Heuristic::Baf => leftover - length.clone(),
+            Heuristic::BottomLeft => free.start.clone() + length.clone(),
+        })
+    }
+}
@@ -0,0 +1,186 @@
+//! Loaders for the benchmark instances of two-dimensional packing used in the literature, so
+//! that published results can be reproduced and configurations compared on known data sets.
+//!
+//! Two formats are supported:
+//!
+//! * The bin packing instances of Berkey and Wang and of Martello and Vigo, in the `.2bp` format
+//!   of the classes 1 to 10 used in Jylänki's evaluation, with several instances per file.
+//! * The strip packing instances of Hopper and Turton, with one instance per file.
+
+use std::error::Error;
+use std::fmt;
+
+use size::Size;
+
+/// A benchmark instance: rectangles to pack into bins of a given size.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Instance {
+    /// The size of every bin. For strip packing instances, this is the width of the strip and
+    /// the optimal height the instance was generated with.
+    pub bin: Size<u32>,
+    /// The sizes of the rectangles, as width and height
+    pub items: Vec<Size<u32>>,
+}
+
+impl Instance {
+    /// The total area of the rectangles
+    pub fn item_area(&self) -> u64 {
+        self.items.iter().map(|x| x.width as u64 * x.height as u64).sum()
+    }
+
+    /// The number of bins the rectangles cover by area, rounded up, which no packing can beat
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bins are empty, which the parsers reject
+    pub fn lower_bound(&self) -> u64 {
+        let bin = self.bin.width as u64 * self.bin.height as u64;
+        (self.item_area() + bin - 1) / bin
+    }
+}
+
+/// An error in the text of a benchmark instance, with the line it occurred on.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ParseInstanceError {
+    /// The line of the error, counted from 1, or the line after the last if the text ended early
+    pub line: usize,
+    /// What was expected on the line
+    pub expected: &'static str,
+}
+
+impl fmt::Display for ParseInstanceError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(formatter, "expected {} on line {}", self.expected, self.line)
+    }
+}
+
+impl Error for ParseInstanceError {}
+
+/// The non-empty lines of a text, with the numbers at the start of each line. The rest of a line
+/// is a description, as in `20 N. OF ITEMS`.
+struct Lines<'a> {
+    lines: ::std::iter::Enumerate<::std::str::Lines<'a>>,
+    count: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn new(text: &'a str) -> Lines<'a> {
+        Lines{lines: text.lines().enumerate(), count: 0}
+    }
+
+    /// Reads the next non-empty line, which must start with at least `count` numbers, or returns
+    /// `None` at the end of the text.
+    fn next(&mut self, count: usize, expected: &'static str) -> Option<Result<Vec<u32>, ParseInstanceError>> {
+        let (index, line) = self.lines.by_ref().find(|&(_, line)| !line.trim().is_empty())?;
+        self.count = index + 1;
+
+        let numbers: Vec<u32> = line.split_whitespace().map_while(|x| x.parse().ok()).collect();
+        Some(if numbers.len() >= count {
+            Ok(numbers)
+        } else {
+            Err(ParseInstanceError{line: self.count, expected: expected})
+        })
+    }
+
+    /// Reads the next non-empty line as `next`, failing at the end of the text
+    fn expect(&mut self, count: usize, expected: &'static str) -> Result<Vec<u32>, ParseInstanceError> {
+        let line = self.count + 1;
+        self.next(count, expected).unwrap_or(Err(ParseInstanceError{line: line, expected: expected}))
+    }
+
+    /// Reads the next non-empty line as `expect`, which must start with the two sides of a bin,
+    /// neither of them zero
+    fn expect_bin(&mut self, expected: &'static str) -> Result<Vec<u32>, ParseInstanceError> {
+        let bin = self.expect(2, expected)?;
+        if bin[0] == 0 || bin[1] == 0 {
+            return Err(ParseInstanceError{line: self.count, expected: expected});
+        }
+        Ok(bin)
+    }
+}
+
+/// Parses the instances of a file in the `.2bp` format. Every instance consists of the lines
+///
+/// ```text
+///  1     PROBLEM CLASS
+///  20    N. OF ITEMS
+///  1  1  RELATIVE AND ABSOLUTE N. OF INSTANCE
+///  10 10 HBIN,WBIN
+///  6  3  H(I),W(I),I=1,...,N
+/// ```
+///
+/// with a line of height and width for each item. Note that heights come before widths.
+pub fn parse_2bp(text: &str) -> Result<Vec<Instance>, ParseInstanceError> {
+    let mut lines = Lines::new(text);
+    let mut instances = Vec::new();
+
+    while let Some(class) = lines.next(1, "the problem class") {
+        class?;
+        let count = lines.expect(1, "the number of items")?[0];
+        lines.expect(2, "the relative and absolute number of the instance")?;
+        let bin = lines.expect_bin("the nonzero height and width of the bins")?;
+
+        let items = (0..count)
+            .map(|_| lines.expect(2, "the height and width of an item").map(|x| Size::new(x[1], x[0])))
+            .collect::<Result<_, _>>()?;
+        instances.push(Instance{bin: Size::new(bin[1], bin[0]), items: items});
+    }
+
+    Ok(instances)
+}
+
+/// Parses a strip packing instance in the format of Hopper and Turton, which consists of the
+/// lines
+///
+/// ```text
+/// 16
+/// 20 20
+/// 2 12
+/// ```
+///
+/// giving the number of items, the width and optimal height of the strip, and then the width and
+/// height of every item.
+pub fn parse_strip(text: &str) -> Result<Instance, ParseInstanceError> {
+    let mut lines = Lines::new(text);
+    let count = lines.expect(1, "the number of items")?[0];
+    let strip = lines.expect_bin("the nonzero width and height of the strip")?;
+
+    let items = (0..count)
+        .map(|_| lines.expect(2, "the width and height of an item").map(|x| Size::new(x[0], x[1])))
+        .collect::<Result<_, _>>()?;
+    Ok(Instance{bin: Size::new(strip[0], strip[1]), items: items})
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_2bp, parse_strip, Instance, ParseInstanceError};
+    use size::Size;
+
+    #[test]
+    fn benchmark_instances() {
+        let text = "   1     PROBLEM CLASS\n   2     N. OF ITEMS\n   1   1 RELATIVE AND ABSOLUTE N. OF INSTANCE\n\
+                    10  20 HBIN,WBIN\n   6   3 H(I),W(I),I=1,...,N\n   4  20\n\n\
+                    1     PROBLEM CLASS\n   1     N. OF ITEMS\n   2   2 RELATIVE AND ABSOLUTE N. OF INSTANCE\n\
+                    10  20 HBIN,WBIN\n  10  20 H(I),W(I),I=1,...,N\n";
+        let instances = parse_2bp(text).unwrap();
+        assert_eq!(instances, vec![
+            Instance{bin: Size::new(20,10), items: vec![Size::new(3,6), Size::new(20,4)]},
+            Instance{bin: Size::new(20,10), items: vec![Size::new(20,10)]},
+        ]);
+        assert_eq!((instances[0].item_area(), instances[0].lower_bound()), (98, 1));
+
+        let truncated: Vec<_> = text.lines().take(11).collect();
+        assert_eq!(parse_2bp(&truncated.join("\n")),
+                   Err(ParseInstanceError{line: 12, expected: "the height and width of an item"}));
+        assert_eq!(parse_2bp("1\nmany\n"), Err(ParseInstanceError{line: 2, expected: "the number of items"}));
+
+        assert_eq!(parse_strip("2\n20 20\n2 12\n18 20\n"),
+                   Ok(Instance{bin: Size::new(20,20), items: vec![Size::new(2,12), Size::new(18,20)]}));
+        assert_eq!(parse_strip("2\n20 20\n2 12\n").unwrap_err().line, 4);
+
+        assert_eq!(parse_2bp(&text.replace("10  20 HBIN", "0  0 HBIN")),
+                   Err(ParseInstanceError{line: 4, expected: "the nonzero height and width of the bins"}));
+        assert_eq!(parse_strip("1\n20 0\n2 12\n"),
+                   Err(ParseInstanceError{line: 2, expected: "the nonzero width and height of the strip"}));
+    }
+}
@@ -0,0 +1,162 @@
+use std::cmp::Ordering;
+use std::ops::{Add, Sub, Range};
+
+use {Heuristic, MinMaxIteratorExt};
+use allocator::{AllocId, SlotTable};
+
+/// A packer of ranges into free ranges of a line, e.g. rows of a texture or slots of a buffer.
+/// This is the one-dimensional counterpart of `RectPacker`, choosing between free ranges by the
+/// same heuristics.
+///
+/// Free ranges never overlap or touch, so a range added back when it is no longer used merges
+/// with the free ranges around it. Ranges can also be allocated by `allocate`, which hands out
+/// the same kind of ids as `AtlasAllocator`, so that they are freed by id and stale ids are
+/// detected. Unlike `GlyphCache`, nothing is evicted to make room.
+#[derive(Clone, PartialEq, Debug)]
+pub struct IntervalPacker<S> {
+    /// The free ranges, ordered by their start
+    free: Vec<Range<S>>,
+    heuristic: Heuristic,
+    /// The allocated ranges, in slots shared with `AtlasAllocator`
+    slots: SlotTable<Range<S>>,
+}
+
+impl<S> IntervalPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> {
+    /// Creates a packer with no free ranges, using `heuristic` to choose between them
+    pub fn new(heuristic: Heuristic) -> IntervalPacker<S> {
+        IntervalPacker{free: Vec::new(), heuristic: heuristic, slots: SlotTable::new()}
+    }
+
+    /// The free ranges, ordered by their start
+    pub fn free_ranges(&self) -> &[Range<S>] {
+        &self.free
+    }
+
+    /// Marks a range as free, e.g. to free a range that was packed, merging it with the free
+    /// ranges it overlaps or touches
+    pub fn add_free(&mut self, range: Range<S>) {
+        if !(range.start < range.end) {
+            return;
+        }
+
+        let (mut start, mut end) = (range.start, range.end);
+        let first = self.free.iter().position(|x| x.end >= start).unwrap_or(self.free.len());
+        let last = self.free.iter().position(|x| x.start > end).unwrap_or(self.free.len());
+        for merged in self.free.drain(first..last) {
+            if merged.start < start { start = merged.start; }
+            if merged.end > end { end = merged.end; }
+        }
+
+        self.free.insert(first, start..end);
+    }
+
+    /// Packs a range of the given length into a free range and returns it, or returns `None` if
+    /// no free range is long enough. Ties are broken in favor of the lowest start.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length is negative
+    pub fn pack(&mut self, length: S) -> Option<Range<S>> {
+        // Subtracting a value from itself gives zero for any scalar
+        let zero = length.clone() - length.clone();
+        if length < zero {
+            panic!("length cannot be negative");
+        }
+
+        let compare = |a: &S, b: &S| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+        let best = self.free.iter()
+            .enumerate()
+            .filter_map(|(index, free)| self.heuristic.score_range(free, &length).map(|score| (index, score)))
+            .min_cmp(|&(_, ref a), &(_, ref b)| compare(a, b));
+
+        best.map(|(index, _)| {
+            let start = self.free[index].start.clone();
+            let end = start.clone() + length;
+            if end < self.free[index].end {
+                self.free[index].start = end.clone();
+            } else {
+                self.free.remove(index);
+            }
+            start..end
+        })
+    }
+
+    /// Packs a range of the given length as `pack` does, and returns it with the id to
+    /// deallocate it with
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length is negative
+    pub fn allocate(&mut self, length: S) -> Option<(AllocId, Range<S>)> {
+        self.pack(length).map(|range| (self.slots.insert(range.clone()), range))
+    }
+
+    /// The range allocated with an id, or `None` if it has been deallocated
+    pub fn get(&self, id: AllocId) -> Option<Range<S>> {
+        self.slots.get(id).cloned()
+    }
+
+    /// Deallocates a range, merging it with the free ranges around it. Does nothing if it has
+    /// already been deallocated.
+    pub fn deallocate(&mut self, id: AllocId) {
+        if let Some(range) = self.slots.remove(id) {
+            self.add_free(range);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IntervalPacker;
+    use Heuristic;
+
+    #[test]
+    fn interval_pack() {
+        let mut packer = IntervalPacker::new(Heuristic::Bssf);
+        packer.add_free(0..10);
+        packer.add_free(20..25);
+
+        assert_eq!(packer.pack(4), Some(20..24));
+        assert_eq!(packer.pack(4), Some(0..4));
+        assert_eq!(packer.pack(7), None);
+
+        packer.add_free(0..4);
+        packer.add_free(24..25);
+        packer.add_free(10..20);
+        assert_eq!(packer.free_ranges(), &[0..20, 24..25]);
+
+        let mut packer = IntervalPacker::new(Heuristic::BottomLeft);
+        packer.add_free(0..10);
+        packer.add_free(20..25);
+        assert_eq!(packer.pack(4), Some(0..4));
+    }
+
+    #[test]
+    fn interval_allocation() {
+        let mut packer = IntervalPacker::new(Heuristic::Bssf);
+        packer.add_free(0..10);
+
+        let (a, range) = packer.allocate(4).unwrap();
+        let (b, _) = packer.allocate(6).unwrap();
+        assert_eq!((packer.get(a), range), (Some(0..4), 0..4));
+        assert_eq!(packer.allocate(1), None);
+
+        packer.deallocate(a);
+        packer.deallocate(a);
+        assert_eq!(packer.get(a), None);
+        let (c, _) = packer.allocate(2).unwrap();
+        assert!(c != a && c.slot() == a.slot() && packer.get(c) == Some(0..2));
+
+        packer.deallocate(b);
+        packer.deallocate(c);
+        assert_eq!(packer.free_ranges(), &[0..10]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn interval_negative_pack() {
+        let mut packer = IntervalPacker::new(Heuristic::Bssf);
+        packer.add_free(0..10);
+        packer.pack(-4);
+    }
+}
@@ -0,0 +1,87 @@
+use std::ops::{Add, Sub, Mul};
+
+use {RectPacker, PackerConfig, Stats, MaybeDebug, MaybeSync};
+use rect::Rect;
+use size::Size;
+
+/// An independent packing job for `RectPacker::run_job`, e.g. one of many atlases packed by a
+/// server.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct PackJob<S> {
+    /// The free rectangles to pack into
+    pub bins: Vec<Rect<S>>,
+    /// The sizes of the rectangles to pack
+    pub sizes: Vec<Size<S>>,
+    /// The options to pack with
+    pub config: PackerConfig<S>,
+}
+
+/// The outcome of a `PackJob`.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct JobResult<S> {
+    /// The packed rectangle for every size of the job, in the same order, or `None` where it
+    /// was not packed
+    pub placements: Vec<Option<Rect<S>>>,
+    /// The counters of the packer for the job alone
+    pub stats: Stats,
+}
+
+impl<S> RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug + MaybeSync {
+    /// Clears this packer, as by `clear`, and globally packs the rectangles of a job as
+    /// `pack_global` does. As the memory of the free list is kept between jobs, running many jobs
+    /// on one packer allocates less than creating a packer for each, though `pack_global` still
+    /// allocates its own buffers for every job.
+    ///
+    /// The outcome does not depend on earlier jobs. If the rectangles cannot all be packed, only
+    /// those placed before the packing failed are returned.
+    pub fn run_job(&mut self, job: PackJob<S>) -> JobResult<S> {
+        self.clear();
+        self.set_config(job.config);
+        for bin in job.bins.into_iter() {
+            self.add_free(bin.min, bin.max);
+        }
+
+        let mut placements = vec![None; job.sizes.len()];
+        let sizes = job.sizes;
+        let packed = match self.pack_global(0..sizes.len(), |&index| sizes[index].clone()) {
+            Ok(packed) => packed.into_vec(),
+            Err(failed) => failed.into_parts().0,
+        };
+        for (index, rect) in packed.into_iter() {
+            placements[index] = Some(rect);
+        }
+
+        JobResult{placements: placements, stats: self.stats()}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {RectPacker, PackerConfig};
+    use rect::Rect;
+    use size::Size;
+    use super::PackJob;
+
+    #[test]
+    fn batch_jobs() {
+        let job = PackJob{
+            bins: vec![Rect::new((0,0), (10,10))],
+            sizes: vec![Size::new(5,10), Size::new(5,5), Size::new(5,5)],
+            config: PackerConfig::default(),
+        };
+
+        let mut packer = RectPacker::new();
+        let first = packer.run_job(job.clone());
+        assert!(first.placements.iter().all(|x| x.is_some()));
+        assert_eq!(packer.utilization(), 1.0);
+
+        let failing = PackJob{bins: vec![Rect::new((0,0), (4,4))], ..job.clone()};
+        assert_eq!(packer.run_job(failing).placements, vec![None; 3]);
+        assert_eq!(packer.bounds(), Some(&Rect::new((0,0), (4,4))));
+
+        let again = packer.run_job(job);
+        assert_eq!(again, first);
+    }
+}
@@ -0,0 +1,114 @@
+use std::cmp::Ordering;
+use std::ops::{Add, Sub, Mul};
+
+use {RectPacker, PackerConfig, MaybeDebug, MinMaxIteratorExt};
+use rect::Rect;
+use size::Size;
+
+/// How a `LayeredPacker` chooses the layer to pack a rectangle into.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum LayerStrategy {
+    /// The first layer it fits in, filling layers one after another
+    FirstFit,
+    /// The layer with the least packed area that it fits in, keeping the layers evenly filled
+    Balanced,
+    /// The layer with the best placement by heuristic, or the first of those that are equally
+    /// good. Rectangles the zero-size policy decides the placement of are packed as by `FirstFit`.
+    BestFit,
+}
+
+/// A packer of rectangles into the layers of a texture array, which all exist from the start and
+/// share the same size.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LayeredPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    layers: Vec<RectPacker<S>>,
+    strategy: LayerStrategy,
+}
+
+impl<S> LayeredPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    /// Creates a packer for `layers` layers, each with `area` free and packed with `config`
+    pub fn new(layers: usize, area: Rect<S>, config: PackerConfig<S>, strategy: LayerStrategy) -> LayeredPacker<S> {
+        let mut layer = RectPacker::with_config(config);
+        layer.add_free(area.min, area.max);
+
+        LayeredPacker{layers: vec![layer; layers], strategy: strategy}
+    }
+
+    /// The packer of every layer
+    pub fn layers(&self) -> &[RectPacker<S>] {
+        &self.layers
+    }
+
+    /// Packs a rectangle into one of the layers, chosen by the strategy, and returns the index
+    /// of the layer and the packed rectangle. Returns `None` if it fits in no layer.
+    pub fn pack(&mut self, width: S, height: S) -> Option<(usize, Rect<S>)> {
+        let size = Size::new(width, height);
+        let compare = |a: &S, b: &S| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+
+        let mut order: Vec<usize> = (0..self.layers.len()).collect();
+        match self.strategy {
+            LayerStrategy::FirstFit => (),
+            LayerStrategy::Balanced => {
+                let layers = &self.layers;
+                order.sort_by(|&a, &b| match (layers[a].packed_area(), layers[b].packed_area()) {
+                    (Some(a), Some(b)) => compare(a, b),
+                    (a, b) => a.is_some().cmp(&b.is_some()),
+                });
+            }
+            LayerStrategy::BestFit => if self.layers.first().map_or(false, |x| x.place_empty(&size).is_none()) {
+                // The layers are only scored, so neither their counters nor their caches change
+                // until the rectangle is packed into the best one.
+                let best = self.layers.iter()
+                    .enumerate()
+                    .filter_map(|(index, layer)| layer.config.best_fit(layer.placements, &layer.empty, &size).map(|x| (index, x)))
+                    .min_cmp(|&(_, ref a), &(_, ref b)| compare(&a.score, &b.score));
+                order = best.map(|(index, _)| index).into_iter().collect();
+            }
+        }
+
+        for index in order.into_iter() {
+            if let Some(rect) = self.layers[index].pack(size.width.clone(), size.height.clone()) {
+                return Some((index, rect));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LayeredPacker, LayerStrategy};
+    use {PackerConfig, ZeroSizePolicy};
+    use rect::Rect;
+
+    #[test]
+    fn layered_pack() {
+        let layers = |strategy| {
+            let mut packer = LayeredPacker::new(2, Rect::new((0,0), (10,10)), PackerConfig::default(), strategy);
+            let packed: Vec<_> = [(5,5), (5,5), (10,5), (10,10)].iter()
+                .map(|&(width, height)| packer.pack(width, height).map(|x| x.0))
+                .collect();
+            packed
+        };
+
+        assert_eq!(layers(LayerStrategy::FirstFit), vec![Some(0), Some(0), Some(0), Some(1)]);
+        assert_eq!(layers(LayerStrategy::Balanced), vec![Some(0), Some(1), Some(0), None]);
+        assert_eq!(layers(LayerStrategy::BestFit), vec![Some(0), Some(0), Some(0), Some(1)]);
+
+        let mut packer = LayeredPacker::new(1, Rect::new((0,0), (10,10)), PackerConfig::default(), LayerStrategy::BestFit);
+        assert_eq!(packer.pack(10,10), Some((0, Rect::new((0,0), (10,10)))));
+        assert_eq!(packer.pack(1,1), None);
+    }
+
+    #[test]
+    fn best_fit_layered_pack() {
+        let mut packer = LayeredPacker::new(2, Rect::new((0,0), (10,10)), PackerConfig::default(), LayerStrategy::BestFit);
+        assert_eq!(packer.pack(5,5), Some((0, Rect::new((0,0), (5,5)))));
+        assert_eq!(packer.layers()[1].stats().heuristic_evaluations, 0);
+
+        let config = PackerConfig{zero_size: ZeroSizePolicy::Origin, ..PackerConfig::default()};
+        let mut packer = LayeredPacker::new(2, Rect::new((0,0), (10,10)), config, LayerStrategy::BestFit);
+        assert_eq!(packer.pack(0,5), Some((0, Rect::new((0,0), (0,5)))));
+    }
+}
@@ -0,0 +1,116 @@
+use std::ops::{Add, Sub, Mul};
+
+use RectPacker;
+use size::Size;
+use point::Point;
+
+/// A physical length from `uom`, as the coordinates of a packer cutting sheet material, so that
+/// bins and pieces can be given in any unit, e.g. millimeters for the sheet and inches for the
+/// pieces, without mixing them up.
+///
+/// The packer multiplies coordinates to compute areas, so the product of two lengths is a length
+/// holding the area in square meters. Such products only appear as areas, e.g. in
+/// `RectPacker::packed_area`, and are converted to actual areas by `Length::to_area`.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub struct Length(pub ::uom::si::f64::Length);
+
+impl Length {
+    /// The area held by the product of two lengths, as computed by the packer
+    pub fn to_area(&self) -> ::uom::si::f64::Area {
+        ::uom::si::f64::Area::new::<::uom::si::area::square_meter>(self.0.value)
+    }
+}
+
+impl RectPacker<Length> {
+    /// The total area of the rectangles packed so far as an actual area, see `packed_area`
+    pub fn packed_physical_area(&self) -> Option<::uom::si::f64::Area> {
+        self.packed_area().map(|x| x.to_area())
+    }
+}
+
+impl Add<Length> for Length {
+    type Output = Length;
+
+    fn add(self, other: Length) -> Length {
+        Length(self.0 + other.0)
+    }
+}
+
+impl Sub<Length> for Length {
+    type Output = Length;
+
+    fn sub(self, other: Length) -> Length {
+        Length(self.0 - other.0)
+    }
+}
+
+impl Mul<Length> for Length {
+    type Output = Length;
+
+    fn mul(self, other: Length) -> Length {
+        Length(::uom::si::f64::Length::new::<::uom::si::length::meter>(self.0.value * other.0.value))
+    }
+}
+
+impl Default for Length {
+    fn default() -> Length {
+        Length(::uom::si::f64::Length::new::<::uom::si::length::meter>(0.0))
+    }
+}
+
+impl From<::uom::si::f64::Length> for Length {
+    fn from(length: ::uom::si::f64::Length) -> Length {
+        Length(length)
+    }
+}
+
+impl From<Length> for ::uom::si::f64::Length {
+    fn from(length: Length) -> ::uom::si::f64::Length {
+        length.0
+    }
+}
+
+/// The length in meters, or the area in square meters for the product of two lengths
+impl From<Length> for f64 {
+    fn from(length: Length) -> f64 {
+        length.0.value
+    }
+}
+
+impl From<(::uom::si::f64::Length, ::uom::si::f64::Length)> for Size<Length> {
+    fn from((width, height): (::uom::si::f64::Length, ::uom::si::f64::Length)) -> Size<Length> {
+        Size::new(Length(width), Length(height))
+    }
+}
+
+impl From<(::uom::si::f64::Length, ::uom::si::f64::Length)> for Point<Length> {
+    fn from((x, y): (::uom::si::f64::Length, ::uom::si::f64::Length)) -> Point<Length> {
+        Point::new(Length(x), Length(y))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use uom::si::f64::Length as Quantity;
+    use uom::si::length::{millimeter, inch};
+    use uom::si::area::square_inch;
+
+    use super::Length;
+    use RectPacker;
+
+    #[test]
+    fn unit_lengths() {
+        let mut packer = RectPacker::new();
+        packer.add_free((Quantity::new::<millimeter>(0.0), Quantity::new::<millimeter>(0.0)),
+                        (Quantity::new::<millimeter>(254.0), Quantity::new::<millimeter>(100.0)));
+
+        let piece = packer.pack(Length(Quantity::new::<inch>(4.0)), Length(Quantity::new::<inch>(2.0))).unwrap();
+        assert!((piece.max.x.0.get::<millimeter>() - 101.6).abs() < 1e-9);
+        let second = packer.pack(Length(Quantity::new::<inch>(6.0)), Length(Quantity::new::<millimeter>(100.0))).unwrap();
+        assert!((second.min.x.0.get::<inch>() - 4.0).abs() < 1e-9);
+        assert_eq!(packer.pack(Length(Quantity::new::<inch>(1.0)), Length(Quantity::new::<inch>(2.0))), None);
+
+        let area = packer.packed_physical_area().unwrap().get::<square_inch>();
+        assert!((area - (8.0 + 6.0 * 100.0 / 25.4)).abs() < 1e-9);
+    }
+}
@@ -2,12 +2,170 @@
 //! "A thousand Ways to Pack the Bin" by Jukka Jylänki
 //! 
 //! Available here: http://clb.demon.fi/files/RectangleBinPack.pdf
+//!
+//! Coordinates may be of any type that can be added, subtracted, multiplied and compared,
+//! including signed integers and floats. Free rectangles may then lie anywhere, such as a bin
+//! from (-512, -512) to (512, 512) in a coordinate space centered on the origin. Rectangles are
+//! always packed towards the minimum corner of a free rectangle, which is the one with the
+//! lowest coordinates regardless of sign. Sizes, padding, row pitches and alignments must not be
+//! negative.
 
-#![allow(unstable)]
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
+#[cfg(feature = "log")]
+#[macro_use]
+extern crate log;
+
+// Without the `log` feature, logging expands to nothing.
+#[cfg(not(feature = "log"))]
+macro_rules! debug { ($($arg:tt)*) => () }
+
+#[cfg(not(feature = "log"))]
+macro_rules! trace { ($($arg:tt)*) => () }
+
+#[cfg(feature = "tracing")]
+extern crate tracing;
+
+#[cfg(feature = "mint")]
+extern crate mint;
+
+#[cfg(feature = "euclid")]
+extern crate euclid;
+
+#[cfg(feature = "glam")]
+extern crate glam;
+
+#[cfg(feature = "serde")]
+extern crate serde;
+
+#[cfg(feature = "wgpu")]
+extern crate wgpu;
+
+#[cfg(feature = "image")]
+extern crate image;
+
+#[cfg(feature = "ab_glyph")]
+extern crate ab_glyph;
+
+#[cfg(feature = "arrayvec")]
+extern crate arrayvec;
+
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+
+#[cfg(feature = "proptest")]
+extern crate proptest;
+
+#[cfg(feature = "quickcheck")]
+extern crate quickcheck;
+
+#[cfg(feature = "uom")]
+extern crate uom;
+
+// Enters a tracing span until the end of the enclosing block, or does nothing without the
+// `tracing` feature.
+#[cfg(feature = "tracing")]
+macro_rules! enter_span {
+    ($($arg:tt)*) => (let _span = ::tracing::debug_span!($($arg)*).entered();)
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! enter_span { ($($arg:tt)*) => () }
 
 use std::fmt;
-use std::ops::{Add, Sub};
-use std::cmp::{partial_min, Ordering};
+use std::hash::Hash;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::time::Instant;
+use std::thread;
+use std::ops::{Add, Sub, Mul};
+use std::cmp::Ordering;
+
+pub use point::Point;
+pub use size::{Size, SizeSource};
+pub use rect::Rect;
+pub use heuristic::Heuristic;
+pub use builder::RectPackerBuilder;
+pub use config::{PackerConfig, ZeroSizePolicy};
+pub use observer::Observer;
+pub use replay::Operation;
+pub use allocator::{AtlasAllocator, Allocation, AllocId, Compaction, AllocationMove};
+pub use glyph_cache::{GlyphCache, GlyphHandle};
+pub use grouped::GroupedRects;
+pub use boxes::{BoxPacker, Cuboid};
+pub use interval::IntervalPacker;
+pub use layers::{LayeredPacker, LayerStrategy};
+pub use concurrent::ConcurrentRectPacker;
+pub use sharded::ShardedRectPacker;
+pub use view::PackerView;
+pub use static_bin::StaticRectPacker;
+pub use score::{PackingScore, score, bins_lower_bound, height_lower_bound};
+pub use report::{PackingReport, BinReport};
+pub use compare::{HeuristicComparison, ComparisonRow};
+pub use grain::Grain;
+pub use dxf::write_dxf;
+pub use job::{PackJob, JobResult};
+pub use scale::{Rounding, scale_size};
+pub use rounded::{RoundedRectPacker, Fractional};
+pub use sink::PlacementSink;
+pub use packed::PackedResult;
+#[cfg(feature = "ab_glyph")]
+pub use glyph::PackedGlyph;
+#[cfg(feature = "arrayvec")]
+pub use fixed::FixedRectPacker;
+#[cfg(feature = "uom")]
+pub use length::Length;
+#[cfg(feature = "wgpu")]
+pub use upload::{TextureUpload, write_packed};
+use cache::FitCache;
+use observer::ObserverSlot;
+
+mod point;
+mod size;
+mod rect;
+mod heuristic;
+mod builder;
+mod config;
+mod cache;
+mod observer;
+mod replay;
+mod allocator;
+mod glyph_cache;
+mod grouped;
+mod boxes;
+mod interval;
+mod layers;
+mod concurrent;
+mod sharded;
+mod view;
+mod static_bin;
+mod score;
+mod waste;
+mod report;
+mod compare;
+mod grain;
+mod offcut;
+mod dxf;
+mod job;
+mod scale;
+mod rounded;
+mod sink;
+mod packed;
+pub mod instances;
+pub mod generate;
+#[cfg(feature = "wgpu")]
+mod upload;
+#[cfg(feature = "ab_glyph")]
+mod glyph;
+#[cfg(feature = "arrayvec")]
+mod fixed;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+#[cfg(feature = "uom")]
+mod length;
 
 trait MinMaxIteratorExt: Iterator + Sized {
     fn min_cmp<F>(self, mut compare: F) -> Option<Self::Item> where
@@ -43,58 +201,291 @@ trait MinMaxIteratorExt: Iterator + Sized {
 
 impl<I> MinMaxIteratorExt for I where I: Iterator {}
 
-#[derive(Clone)]
-struct Rectangle<S> {
-    min: (S,S),
-    max: (S,S),
+/// The best placement of a rectangle without rotating it, and of it rotated if allowed.
+type Fits<S> = (Option<Candidate<S>>, Option<Candidate<S>>);
+
+/// Scrambles the bits of a number, as the finalizer of the SplitMix64 generator.
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e3779b97f4a7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+/// Sums the values of an iterator, or returns `None` if it is empty.
+fn sum<S, I>(values: I) -> Option<S> where I: Iterator<Item=S>, S: Add<S, Output=S> {
+    values.fold(None, |sum, value| Some(match sum {
+        Some(sum) => sum + value,
+        None => value,
+    }))
+}
+
+/// Rounds a positive value up to a multiple of `multiple`. Only addition and subtraction are
+/// available, so the multiple is found by doubling and then bisecting.
+fn round_up<S>(value: S, multiple: &S) -> S where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> {
+    let mut steps = vec![multiple.clone()];
+    while *steps.last().unwrap() < value {
+        let step = steps.last().unwrap().clone();
+        steps.push(step.clone() + step);
+    }
+
+    let mut rounded = steps.pop().unwrap();
+    for step in steps.into_iter().rev() {
+        if rounded.clone() - step.clone() >= value {
+            rounded = rounded - step;
+        }
+    }
+    rounded
+}
+
+/// The Manhattan distance between two points.
+fn manhattan<S>(a: &Point<S>, b: &Point<S>) -> S where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> {
+    let difference = |a: &S, b: &S| if *a < *b { b.clone() - a.clone() } else { a.clone() - b.clone() };
+    difference(&a.x, &b.x) + difference(&a.y, &b.y)
+}
+
+/// A possible placement of a rectangle, as found by `RectPacker::fit`.
+#[derive(Clone, Debug)]
+struct Candidate<S> {
+    /// The free rectangle it is placed in
+    free: Rect<S>,
+    /// The position of the minimum corner
+    position: Point<S>,
+    /// The size of the rectangle as placed, with the sides swapped if it was rotated
+    size: Size<S>,
+    /// The heuristic score, where lower is better
+    score: S,
+    /// A pseudo-random key for breaking ties, which is zero unless `PackerConfig::tie_seed` is set
+    tie: u64,
+}
+
+impl<S> Candidate<S> where S: PartialOrd {
+    /// Orders two placements so that the best placement is the least. Placements with equal
+    /// scores are ordered by their tie-breaking keys, then by the lowest y coordinate and then by
+    /// the lowest x coordinate, so that the chosen placement never depends on the order of the
+    /// free rectangles.
+    fn compare(&self, other: &Candidate<S>) -> Ordering {
+        let compare = |x: &S, y: &S| x.partial_cmp(y).unwrap_or(Ordering::Equal);
+
+        match compare(&self.score, &other.score) {
+            Ordering::Equal => match self.tie.cmp(&other.tie) {
+                Ordering::Equal => match compare(&self.position.y, &other.position.y) {
+                    Ordering::Equal => compare(&self.position.x, &other.position.x),
+                    ordering => ordering
+                },
+                ordering => ordering
+            },
+            ordering => ordering
+        }
+    }
+
+    /// Chooses between the best placement of a rectangle and the best placement of it rotated,
+    /// in favor of not rotating it.
+    fn choose(best: Option<Candidate<S>>, rotated: Option<Candidate<S>>) -> Option<Candidate<S>> {
+        match (best, rotated) {
+            (Some(best), Some(rotated)) => match rotated.compare(&best) {
+                Ordering::Less => Some(rotated),
+                _ => Some(best),
+            },
+            (best, rotated) => best.or(rotated),
+        }
+    }
 }
 
-impl<S> Rectangle<S> {
-    fn new(min: (S,S), max: (S,S)) -> Rectangle<S> {
-        Rectangle{min: min, max: max}
+/// The search for placements, shared by every packer placing rectangles according to a
+/// `PackerConfig`, so that they place them alike.
+impl<S> PackerConfig<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> {
+    /// The number of orientations tried when packing a rectangle of size `size`
+    fn orientations(&self, size: &Size<S>) -> usize {
+        if self.allow_rotation && size.width != size.height { 2 } else { 1 }
+    }
+
+    /// The key for breaking ties in favor of the free rectangle at `index` in the free list, after
+    /// `placements` rectangles have been placed. With a seed, the keys are shuffled after every
+    /// placement, so they are only comparable within a search of the entire free list.
+    fn tie_key(&self, placements: u64, index: usize) -> u64 {
+        match self.tie_seed {
+            Some(seed) => mix(mix(seed ^ mix(placements)) ^ index as u64),
+            None => 0,
+        }
+    }
+
+    /// Every placement of a rectangle in the free rectangles `rects` without rotating it, after
+    /// `placements` rectangles have been placed.
+    fn candidates<'a>(&'a self, placements: u64, rects: &'a [Rect<S>], size: &Size<S>) -> impl Iterator<Item=Candidate<S>> + 'a {
+        let (padded, size) = (self.padded_size(size), size.clone());
+
+        rects.iter()
+            .enumerate()
+            .filter_map(move |(index, x)| self.heuristic.score(x, &padded).map(|score| Candidate{
+                free: x.clone(),
+                position: x.min.clone(),
+                size: size.clone(),
+                score: score,
+                tie: self.tie_key(placements, index),
+            }))
+    }
+
+    /// The best placement of a rectangle in the free rectangles `rects`, rotating it if that is
+    /// allowed and better, as `RectPacker::pack` chooses it without its cache.
+    fn best_fit(&self, placements: u64, rects: &[Rect<S>], size: &Size<S>) -> Option<Candidate<S>> {
+        let fit = |size: &Size<S>| self.candidates(placements, rects, size).min_cmp(|a, b| a.compare(b));
+        let best = fit(size);
+        if self.orientations(size) == 1 {
+            return best;
+        }
+        Candidate::choose(best, fit(&Size::new(size.height.clone(), size.width.clone())))
+    }
+
+    /// Where a rectangle of size `size` is placed under the zero-size policy, if it is empty and
+    /// not packed like any other rectangle. It is then either not placed at all, or placed at the
+    /// minimum corner of `bounds`.
+    fn place_empty(&self, bounds: Option<&Rect<S>>, size: &Size<S>) -> Option<Option<Rect<S>>> {
+        if self.zero_size == ZeroSizePolicy::Pack || !size.is_empty() {
+            return None;
+        }
+
+        Some(match self.zero_size {
+            ZeroSizePolicy::Origin => bounds.map(|x| Rect::with_size(x.min.clone(), size.clone())),
+            _ => None,
+        })
+    }
+
+    /// Checks a rectangle of size `size` about to be packed and applies the zero-size policy to
+    /// it, returning where it is placed if that decides it, as `place_empty` does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width or height is negative
+    fn admit(&self, bounds: Option<&Rect<S>>, size: &Size<S>) -> Option<Option<Rect<S>>> {
+        if size.is_negative() {
+            panic!("width and height cannot be negative");
+        }
+        self.place_empty(bounds, size)
     }
 }
 
-impl<S> Rectangle<S> where S: PartialOrd {
-    /// Whether a rectangle intersects another rectangle
-    fn intersects(&self, other: &Rectangle<S>) -> bool {
-        self.min.0 < other.max.0 &&
-        self.min.1 < other.max.1 &&
-        self.max.0 > other.min.0 &&
-        self.max.1 > other.min.1
+/// Suggests the size of a bin for packing rectangles of the given sizes, as a starting point for
+/// searching for the smallest bin they fit in. The bin has an area such that the rectangles cover
+/// `target_utilization` of it, and is as close to square as it can be while fitting the widest
+/// and the tallest rectangle. Both sides are rounded up to whole numbers.
+///
+/// A `target_utilization` below 1 leaves slack for the space lost to packing; values around
+/// 0.8 to 0.9 are typical.
+pub fn estimate_bin_size<S, Z>(sizes: &[Z], target_utilization: f64) -> Size<f64> where Z: Clone + Into<Size<S>>, S: Into<f64> {
+    let mut area = 0.0;
+    let mut size = Size::new(0.0f64, 0.0f64);
+    for x in sizes.iter() {
+        let Size{width, height} = x.clone().into();
+        let (width, height): (f64, f64) = (width.into(), height.into());
+        area += width * height;
+        size.width = size.width.max(width);
+        size.height = size.height.max(height);
     }
 
-    /// Determines if a rectangle is a superset of (contains all of) another rectangle
-    fn supersets(&self, other: &Rectangle<S>) -> bool {
-        self.min.0 <= other.min.0 &&
-        self.min.1 <= other.min.1 &&
-        self.max.0 >= other.max.0 &&
-        self.max.1 >= other.max.1
+    // Stretching one side to fit a rectangle lets the other side shrink to keep the area.
+    let area = area / target_utilization;
+    let width = area.sqrt().max(size.width);
+    let height = (area / width).max(size.height);
+    let width = (area / height).max(size.width);
+    Size::new(width.ceil(), height.ceil())
+}
+
+/// A bound on the scalar type for operations that may run in parallel. With the `rayon` feature
+/// enabled this requires `Send + Sync`, and otherwise it is implemented for every type.
+#[cfg(feature = "rayon")]
+pub trait MaybeSync: Send + Sync {}
+
+#[cfg(feature = "rayon")]
+impl<S> MaybeSync for S where S: Send + Sync {}
+
+/// A bound on the scalar type for operations that may run in parallel. With the `rayon` feature
+/// enabled this requires `Send + Sync`, and otherwise it is implemented for every type.
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "rayon"))]
+impl<S> MaybeSync for S {}
+
+/// A bound on the scalar type for logging coordinates. With the `log` feature enabled this
+/// requires `Debug`, and otherwise it is implemented for every type.
+#[cfg(feature = "log")]
+pub trait MaybeDebug: fmt::Debug {}
+
+#[cfg(feature = "log")]
+impl<S> MaybeDebug for S where S: fmt::Debug {}
+
+/// A bound on the scalar type for logging coordinates. With the `log` feature enabled this
+/// requires `Debug`, and otherwise it is implemented for every type.
+#[cfg(not(feature = "log"))]
+pub trait MaybeDebug {}
+
+#[cfg(not(feature = "log"))]
+impl<S> MaybeDebug for S {}
+
+/// An order in which to insert rectangles when packing them one at a time. Each order sorts the
+/// rectangles descendingly, so the largest rectangle by the given measure is packed first.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum SortOrder {
+    /// Sort by area
+    AreaDesc,
+    /// Sort by the shorter side
+    ShortSideDesc,
+    /// Sort by the longer side
+    LongSideDesc,
+    /// Sort by perimeter
+    PerimeterDesc,
+}
+
+impl SortOrder {
+    /// Computes the value to sort a rectangle of size `size` by.
+    fn key<S>(&self, size: &Size<S>) -> S where S: Clone + PartialOrd + Add<S, Output=S> + Mul<S, Output=S> {
+        let Size{width, height} = size.clone();
+        match *self {
+            SortOrder::AreaDesc => width * height,
+            SortOrder::ShortSideDesc => if width < height { width } else { height },
+            SortOrder::LongSideDesc => if width < height { height } else { width },
+            SortOrder::PerimeterDesc => width + height,
+        }
     }
 }
 
-impl<S> Rectangle<S> where S: Clone + PartialOrd + Sub<S, Output=S> {
-    fn dimensions(&self) -> (S,S) {
-        (self.max.0.clone() - self.min.0.clone(), self.max.1.clone() - self.min.1.clone())
+/// A condition under which a long-running packing stops early, see
+/// `RectPacker::pack_global_interruptible`.
+pub trait Interrupt {
+    /// Whether the packing should stop now
+    fn interrupted(&mut self) -> bool;
+}
+
+/// Interrupts once the flag is set, e.g. from another thread.
+impl<'a> Interrupt for &'a AtomicBool {
+    fn interrupted(&mut self) -> bool {
+        self.load(AtomicOrdering::Relaxed)
     }
 }
 
-/// Returns the best-short-side heuristic if applicaple, and `None` if not.
-fn bssf<S>(sup: &(S,S), sub: &(S,S)) -> Option<S> where S: Clone + PartialOrd + Sub<S, Output=S> {
-    if sup.0 >= sub.0 && sup.1 >= sub.1 {
-        partial_min(sup.0.clone() - sub.0.clone(), sup.1.clone() - sub.1.clone())
-    } else {
-        None
+/// Interrupts once the instant, as a deadline, has been reached.
+impl Interrupt for Instant {
+    fn interrupted(&mut self) -> bool {
+        Instant::now() >= *self
     }
 }
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct FailedPacking<T,S> {
-    partial_packed: Vec<(T, (S,S))>,
+    partial_packed: Vec<(T, Rect<S>)>,
     original: Vec<T>,
+    interrupted: bool,
 }
 
 impl<T,S> fmt::Display for FailedPacking<T,S> {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        formatter.write_str("No possible rectangle packing found")
+        if self.interrupted {
+            formatter.write_str("Rectangle packing was interrupted")
+        } else {
+            formatter.write_str("No possible rectangle packing found")
+        }
     }
 }
 
@@ -107,17 +498,408 @@ impl<T,S> FailedPacking<T,S> {
 
         self.original
     }
+
+    /// The objects that were packed before the packing failed, with their rectangles
+    pub fn packed(&self) -> &[(T, Rect<S>)] {
+        &self.partial_packed
+    }
+
+    /// The objects that were not packed
+    pub fn unpacked(&self) -> &[T] {
+        &self.original
+    }
+
+    /// Whether the packing failed because it was interrupted, rather than because the remaining
+    /// objects could not be packed
+    pub fn interrupted(&self) -> bool {
+        self.interrupted
+    }
+
+    /// Splits the failed packing into the objects that were packed, with their rectangles, and
+    /// the objects that were not
+    pub fn into_parts(self) -> (Vec<(T, Rect<S>)>, Vec<T>) {
+        (self.partial_packed, self.original)
+    }
+}
+
+/// The progress of a global packing, as reported after every placement by
+/// `RectPacker::pack_global_with_progress`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Progress<S> {
+    /// The number of objects packed so far
+    pub placed: usize,
+    /// The number of objects not yet packed
+    pub remaining: usize,
+    /// The total area of the objects packed so far
+    pub packed_area: S,
+    /// The area of the bounding box of all free rectangles added to the packer
+    pub bin_area: S,
+}
+
+impl<S> Progress<S> where S: Clone + Into<f64> {
+    /// The fraction of the bin area covered by the objects packed so far
+    pub fn utilization(&self) -> f64 {
+        self.packed_area.clone().into() / self.bin_area.clone().into()
+    }
+}
+
+/// Counters describing the work done by a `RectPacker`, as returned by `RectPacker::stats`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Stats {
+    /// The number of rectangles that have been attempted packed, either by `pack` or as one of the
+    /// objects given to `pack_global`
+    pub packs_attempted: usize,
+    /// The number of times a free rectangle has been scored by the heuristic
+    pub heuristic_evaluations: usize,
+    /// The highest number of free rectangles held at once
+    pub peak_free: usize,
+    /// The number of passes over the free rectangles discarding those contained in another, one
+    /// for every free rectangle added or split off
+    pub prune_passes: usize,
+    /// The number of free rectangles discarded for being contained in another free rectangle
+    pub pruned_rects: usize,
+}
+
+#[derive(Clone)]
+pub struct RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    empty: Vec<Rect<S>>,
+    max_free: Option<usize>,
+    stats: Stats,
+    bounds: Option<Rect<S>>,
+    packed_area: Option<S>,
+    queue: Vec<(usize, Size<S>)>,
+    dequeued: Vec<(usize, Rect<S>)>,
+    next_ticket: usize,
+    config: PackerConfig<S>,
+    cache: FitCache<S>,
+    observer: ObserverSlot<S>,
+    recording: Option<Vec<Operation<S>>>,
+    placements: u64,
+}
+
+impl<S> fmt::Debug for RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug + fmt::Debug {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        formatter.debug_struct("RectPacker")
+            .field("bounds", &self.bounds)
+            .field("free", &self.empty)
+            .field("packed_area", &self.packed_area)
+            .field("config", &self.config)
+            .field("max_free", &self.max_free)
+            .field("queue", &self.queue)
+            .field("observer", &self.observer)
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+/// Packers are equal when they would pack the same, i.e. when their bounds, free rectangles,
+/// placement options, free rectangle limit and queued rectangles are, regardless of how they got
+/// there. The order of free rectangles starting at the same x coordinate depends on that, so it is
+/// not compared.
+impl<S> PartialEq for RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    fn eq(&self, other: &RectPacker<S>) -> bool {
+        self.bounds == other.bounds &&
+            self.config == other.config &&
+            self.max_free == other.max_free &&
+            self.queue == other.queue &&
+            self.empty.len() == other.empty.len() &&
+            self.empty.iter().all(|x| other.is_free(x))
+    }
 }
 
-pub struct RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> {
-    empty: Vec<Rectangle<S>>,
+/// Summarizes the packer on one line, giving its bounds, the number of free rectangles and the
+/// fraction of the bounds covered by packed rectangles.
+impl<S> fmt::Display for RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug + Into<f64> + fmt::Display {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self.bounds {
+            Some(ref bounds) => write!(formatter, "RectPacker ({}, {})..({}, {}): ",
+                                       bounds.min.x, bounds.min.y, bounds.max.x, bounds.max.y)?,
+            None => formatter.write_str("RectPacker without bounds: ")?,
+        }
+
+        write!(formatter, "{} free rectangles, {:.1}% utilized", self.empty.len(), self.utilization() * 100.0)
+    }
 }
 
-impl<S> RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> {
+impl<S> RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
     /// Creates a new, empty RectPacker
     #[inline]
     pub fn new() -> RectPacker<S> {
-        RectPacker{empty: Vec::new()}
+        RectPacker::with_capacity(0)
+    }
+
+    /// Creates a new, empty RectPacker with space for at least `capacity` free rectangles
+    /// without reallocating
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> RectPacker<S> {
+        RectPacker{
+            empty: Vec::with_capacity(capacity),
+            max_free: None,
+            stats: Default::default(),
+            bounds: None,
+            packed_area: None,
+            queue: Vec::new(),
+            dequeued: Vec::new(),
+            next_ticket: 0,
+            cache: FitCache::new(),
+            observer: ObserverSlot::new(),
+            recording: None,
+            placements: 0,
+            config: Default::default(),
+        }
+    }
+
+    /// Creates a new, empty RectPacker placing rectangles according to `config`
+    #[inline]
+    pub fn with_config(config: PackerConfig<S>) -> RectPacker<S> {
+        let mut packer = RectPacker::new();
+        packer.config = config;
+        packer
+    }
+
+    /// Creates a builder for configuring a new RectPacker
+    #[inline]
+    pub fn builder() -> RectPackerBuilder<S> {
+        RectPackerBuilder::new()
+    }
+
+    /// Reserves space for at least `additional` more free rectangles without reallocating
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.empty.reserve(additional);
+    }
+
+    /// Removes all free rectangles and queued rectangles and resets the bounds, the packed area
+    /// and the counters, keeping the options and the memory allocated, so that the packer can be
+    /// reused for something else as if it were new. A recording in progress starts over.
+    pub fn clear(&mut self) {
+        self.empty.clear();
+        self.queue.clear();
+        self.dequeued.clear();
+        self.cache.clear();
+        self.bounds = None;
+        self.packed_area = None;
+        self.placements = 0;
+        self.stats = Default::default();
+
+        if self.recording.is_some() {
+            self.start_recording();
+        }
+    }
+
+    /// The free rectangles that can currently be packed into, none of which contains another
+    #[inline]
+    pub fn free_rects(&self) -> &[Rect<S>] {
+        &self.empty
+    }
+
+    /// The bounding box of all free rectangles added with `add_free`, or `None` if none have been
+    /// added
+    #[inline]
+    pub fn bounds(&self) -> Option<&Rect<S>> {
+        self.bounds.as_ref()
+    }
+
+    /// The total area of the rectangles packed so far, excluding padding, or `None` if none have
+    /// been packed
+    ///
+    /// The area is the product of coordinates, so with the `Length` coordinates of the `uom`
+    /// feature it is a `Length` holding square meters. `packed_physical_area` returns it as an
+    /// actual area instead.
+    #[inline]
+    pub fn packed_area(&self) -> Option<&S> {
+        self.packed_area.as_ref()
+    }
+
+    /// The fraction of the bounds covered by the rectangles packed so far, or zero if nothing has
+    /// been packed
+    pub fn utilization(&self) -> f64 where S: Into<f64> {
+        match (&self.packed_area, &self.bounds) {
+            (&Some(ref area), &Some(ref bounds)) => area.clone().into() / bounds.area().into(),
+            _ => 0.0,
+        }
+    }
+
+    /// The options rectangles are placed according to
+    #[inline]
+    pub fn config(&self) -> &PackerConfig<S> {
+        &self.config
+    }
+
+    /// Replaces the options rectangles are placed according to. This only affects rectangles
+    /// packed from now on.
+    pub fn set_config(&mut self, config: PackerConfig<S>) {
+        self.record(Operation::SetConfig(config.clone()));
+        self.config = config;
+        self.cache.clear();
+    }
+
+    /// Sets an observer to be notified of every placement and every change to the free
+    /// rectangles from now on, replacing any previous observer. Clones of the packer are not
+    /// notified.
+    pub fn set_observer<O>(&mut self, observer: O) where O: Observer<S> + 'static {
+        self.observer.set(Some(Box::new(observer)));
+    }
+
+    /// Removes the observer set by `set_observer` and returns it
+    pub fn take_observer(&mut self) -> Option<Box<dyn Observer<S>>> {
+        self.observer.set(None)
+    }
+
+    /// Starts recording every public operation changing the packer into a log that can be
+    /// re-executed by `replay`, discarding any previous recording. The recording begins with
+    /// operations recreating the current options and free rectangles, so replaying it on a new
+    /// packer reproduces this one.
+    pub fn start_recording(&mut self) {
+        let mut recording = vec![Operation::SetConfig(self.config.clone()), Operation::SetMaxFree(self.max_free)];
+        recording.extend(self.empty.iter().map(|x| Operation::AddFree(x.clone())));
+        self.recording = Some(recording);
+    }
+
+    /// Stops recording and returns the operations recorded since `start_recording`, if it was
+    /// called.
+    pub fn stop_recording(&mut self) -> Option<Vec<Operation<S>>> {
+        self.recording.take()
+    }
+
+    /// Re-executes recorded operations on this packer.
+    pub fn replay(&mut self, operations: &[Operation<S>]) where S: MaybeSync {
+        for operation in operations.iter() {
+            match *operation {
+                Operation::AddFree(ref rect) => self.add_free(rect.min.clone(), rect.max.clone()),
+                Operation::GrowTo(ref max) => self.grow_to(max.clone()),
+                Operation::Pack(ref size) => { self.pack(size.width.clone(), size.height.clone()); },
+                Operation::PackNear(ref size, ref near) => {
+                    self.pack_near(size.width.clone(), size.height.clone(), near);
+                },
+                Operation::PackGlobal(ref sizes, placements) => {
+                    let mut checks = 0;
+                    let _ = self.pack_global_impl(sizes.clone(), |x| x.clone(), |_| (), || {
+                        checks += 1;
+                        checks > placements
+                    });
+                },
+                Operation::PackGlobalWithGrain(ref items, placements) => {
+                    let mut checks = 0;
+                    let sizes = items.iter().map(|&(ref size, grain)| grain.orient(size.clone())).collect();
+                    let _ = self.pack_global_sizes(items.clone(), sizes, |_| (), || {
+                        checks += 1;
+                        checks > placements
+                    });
+                    self.record(Operation::PackGlobalWithGrain(items.clone(), placements));
+                },
+                Operation::SetMaxFree(limit) => self.set_max_free(limit),
+                Operation::SetConfig(ref config) => self.set_config(config.clone()),
+            }
+        }
+    }
+
+    /// Re-executes only the packing operations of a recording on this packer, with its own free
+    /// rectangles and options, e.g. to see how a layout recorded in one bin would pack into a
+    /// larger bin or with another heuristic. Global packings are redone in full, packing as many
+    /// of their objects as fit.
+    ///
+    /// Returns a placement for every rectangle packed by the operations, as given by
+    /// `Operation::sizes` in the order the operations were recorded, so that the index of an
+    /// item identifies it across replays. Rectangles that no longer fit are `None`.
+    pub fn replay_packing(&mut self, operations: &[Operation<S>]) -> Vec<Option<Rect<S>>> where S: MaybeSync {
+        let mut placements = Vec::new();
+        for operation in operations.iter() {
+            let start = placements.len();
+            placements.extend(operation.sizes().into_iter().map(|_| None));
+            let packed = match *operation {
+                Operation::Pack(ref size) => {
+                    self.pack(size.width.clone(), size.height.clone()).map(|x| (0, x)).into_iter().collect()
+                },
+                Operation::PackNear(ref size, ref near) => {
+                    self.pack_near(size.width.clone(), size.height.clone(), near).map(|x| (0, x)).into_iter().collect()
+                },
+                Operation::PackGlobal(ref sizes, _) => {
+                    match self.pack_global(0..sizes.len(), |&index| sizes[index].clone()) {
+                        Ok(packed) => packed.into_vec(),
+                        Err(failed) => failed.into_parts().0,
+                    }
+                },
+                Operation::PackGlobalWithGrain(ref items, _) => {
+                    match self.pack_global_with_grain((0..items.len()).collect(), |&index| items[index].0.clone(), |&index| items[index].1) {
+                        Ok(packed) => packed.into_vec(),
+                        Err(failed) => failed.into_parts().0,
+                    }
+                },
+                Operation::AddFree(_) | Operation::GrowTo(_) | Operation::SetMaxFree(_) | Operation::SetConfig(_) => Vec::new(),
+            };
+
+            for (index, rect) in packed.into_iter() {
+                placements[start + index] = Some(rect);
+            }
+        }
+
+        placements
+    }
+
+    /// Appends an operation to the recording, if recording.
+    fn record(&mut self, operation: Operation<S>) {
+        if let Some(ref mut recording) = self.recording {
+            recording.push(operation);
+        }
+    }
+
+    /// Limits the number of free rectangles retained to `limit`, or removes the limit if `None`.
+    /// Whenever there are more free rectangles, the smallest ones by area are dropped, so that
+    /// the space they cover can no longer be packed into.
+    ///
+    /// This bounds the memory and time used by the packer at the expense of packing quality.
+    pub fn set_max_free(&mut self, limit: Option<usize>) {
+        self.record(Operation::SetMaxFree(limit));
+        self.max_free = limit;
+        self.truncate_free();
+        self.refresh_cache();
+    }
+
+    /// The maximum number of free rectangles retained, if limited.
+    #[inline]
+    pub fn max_free(&self) -> Option<usize> {
+        self.max_free
+    }
+
+    /// Counters describing the work done by the packer since it was created or the counters
+    /// were last reset.
+    #[inline]
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Resets all counters returned by `stats`.
+    pub fn reset_stats(&mut self) {
+        self.stats = Default::default();
+        self.stats.peak_free = self.empty.len();
+    }
+
+    /// Records the current number of free rectangles if it is the highest yet.
+    fn update_peak(&mut self) {
+        if self.empty.len() > self.stats.peak_free {
+            self.stats.peak_free = self.empty.len();
+        }
+    }
+
+    /// Drops the smallest free rectangles by area until the limit set by `set_max_free` is met.
+    fn truncate_free(&mut self) {
+        if let Some(limit) = self.max_free {
+            while self.empty.len() > limit {
+                let smallest = self.empty.iter()
+                    .map(|x| x.area())
+                    .enumerate()
+                    .min_cmp(|&(_, ref a), &(_, ref b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+                    .map(|(index, _)| index)
+                    .unwrap();
+
+                trace!("dropped free rectangle {:?}..{:?} over the limit of {}",
+                       self.empty[smallest].min, self.empty[smallest].max, limit);
+                let dropped = self.empty.remove(smallest);
+                self.observer.remove(&dropped);
+            }
+        }
     }
 
     /// Adds a rectangle defined by a minimum coordinate and a maximum coordinate to the list of
@@ -127,178 +909,1144 @@ impl<S> RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Ou
     /// This does not have to be disjoint of any previous free rectangle. This may be a previously
     /// packed rectangle, but further optimal packing cannot be guarenteed in that case.
     ///
+    /// Any rectangles queued by `pack_or_queue` are then packed into the new space if they fit,
+    /// see `drain_queued`.
+    ///
     /// # Panics
     /// 
     /// Panics if either x or y in `min` is more than `max`
-    pub fn add_free(&mut self, min: (S,S), max: (S,S)) {
-        if min.0 > max.0 {
-            panic!("min.0 cannot be more than max.0");
+    pub fn add_free<A, B>(&mut self, min: A, max: B) where A: Into<Point<S>>, B: Into<Point<S>> {
+        let rect = Rect::new(min, max);
+
+        if rect.min.x > rect.max.x {
+            panic!("min.x cannot be more than max.x");
         }
 
-        if min.1 > max.1 {
-            panic!("min.1 cannot be more than max.1");
+        if rect.min.y > rect.max.y {
+            panic!("min.y cannot be more than max.y");
         }
 
-        self.empty.push(Rectangle::new(min, max));
-    }
+        self.record(Operation::AddFree(rect.clone()));
 
-    /// Retrieves the best (by heuristic) free rectangle within a certain size.
-    fn optimal(&self, size: &(S,S)) -> Option<((S,S), S)> {
-        self.empty.iter()
-            .filter_map(|x| bssf(&x.dimensions(), size).map(|h| (x,h)))
-            .min_cmp(|&(_, ref a), &(_, ref b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
-            .map(|(x,h)| (x.min.clone(), h))
-    }
+        self.bounds = Some(match self.bounds {
+            Some(ref bounds) => bounds.union(&rect),
+            None => rect.clone(),
+        });
 
-    /// Packs a rectangle into a free rectangle, so that it does not intersect any previously
-    /// packed rectangles. If a suitable position is found, it is returned. Otherwise `None`
-    /// is returned.
-    pub fn pack(&mut self, width: S, height: S) -> Option<(S, S)> {
-        //TODO: Check for negative width and height
-
-        if let Some((position, _)) = self.optimal(&(width.clone(), height.clone())) {
-            let max = (position.0.clone() + width, position.1.clone() + height);
-            self.subtract_rect(&Rectangle::new(position.clone(), max));
-            Some(position)
-        } else {
-            None
-        }
+        self.insert_free(rect);
+        self.truncate_free();
+        self.update_peak();
+        self.refresh_cache();
+        self.pack_queued();
     }
 
-    /// Removes a rectangle from the list of free rectangles, so that no remaining free rectangle
-    /// intersects with this rectangle
-    fn subtract_rect(&mut self, sub: &Rectangle<S>) {
-        
-        // We keep track of the 'derived' rectangles. These are the rectangles at the end of the
-        // free list that were added earlier during the process. Since we know these do not
-        // intersect `sub` they can be skipped.
-        let mut derived = 0;
-        let mut index = 0;
-        while index < self.empty.len() - derived {
-
-            let free = self.empty[index].clone();
-
-            if free.intersects(sub) {
-                {
-                    let mut push = |&mut: min, max| self.empty.push(Rectangle::new(min,max));
-
-                    if sub.min.0 > free.min.0 {
-                        push(free.min.clone(),(sub.min.0.clone(), free.max.1.clone()));
-                        derived += 1;
-                    }
-
-                    if sub.min.1 > free.min.1 {
-                        push(free.min.clone(), (free.max.0.clone(), sub.min.1.clone()));
-                        derived += 1;
-                    }
-
-                    if sub.max.0 < free.max.0 {
-                        push((sub.max.0.clone(), free.min.1.clone()), free.max.clone());
-                        derived += 1;
-                    }
+    /// Removes a rectangle from the free rectangles as packing it there would, without packing
+    /// it or recording the change. This is only public so that splitting the free rectangles can
+    /// be benchmarked on its own.
+    #[doc(hidden)]
+    pub fn subtract_free(&mut self, rect: &Rect<S>) {
+        self.subtract_rect(rect);
+        self.refresh_cache();
+    }
 
-                    if sub.max.1 < free.max.1 {
-                        push((free.min.0.clone(), sub.max.1.clone()), free.max.clone());
-                        derived += 1;
-                    }
-                }
+    /// Grows the bounds so that their maximum is `max`, making the newly exposed space free, e.g.
+    /// to enlarge an atlas in place once it is full. Unlike adding that space with `add_free`,
+    /// the free rectangles along the maximum edges of the bounds are extended into it, so the
+    /// free rectangles stay maximal and packing is as good as if the bounds had been this large
+    /// from the start. A coordinate of `max` less than that of the bounds leaves that side as it
+    /// is, and without bounds this does nothing. Only a grow that changes the bounds is
+    /// recorded.
+    ///
+    /// Any rectangles queued by `pack_or_queue` are then packed into the new space if they fit,
+    /// see `drain_queued`.
+    pub fn grow_to<P>(&mut self, max: P) where P: Into<Point<S>> {
+        let max = max.into();
+        let old = match self.bounds {
+            Some(ref bounds) => bounds.clone(),
+            None => return,
+        };
+        let grow_x = old.max.x < max.x;
+        let grow_y = old.max.y < max.y;
+        if !grow_x && !grow_y {
+            return;
+        }
 
-                self.empty.swap_remove(index);
+        let max = Point::new(if grow_x { max.x } else { old.max.x.clone() },
+                             if grow_y { max.y } else { old.max.y.clone() });
+        self.record(Operation::GrowTo(max.clone()));
+        debug!("grew bounds {:?}..{:?} to {:?}", old.min, old.max, max);
 
-                // If derived rectangles have been added to the end, we do not
-                // intersect it, and we can skip it.
-                if derived > 0 {
-                    derived -= 1;
-                    index += 1;
-                }
-            } else {
-                index += 1;
+        // With a kerf, the free rectangles at the far edges extend past the bounds by the cut
+        // that is not needed there, which moves along with the edges.
+        let mut extent = old.max.clone();
+        for free in self.empty.iter() {
+            if free.max.x > extent.x {
+                extent.x = free.max.x.clone();
+            }
+            if free.max.y > extent.y {
+                extent.y = free.max.y.clone();
             }
         }
+        let grown_extent = Point::new(max.x.clone() + (extent.x - old.max.x.clone()),
+                                      max.y.clone() + (extent.y - old.max.y.clone()));
 
-        // Compares all rectangles pairwise and removes any that is a subset of another rectangle
-        let mut i = 0;
-        while i < self.empty.len() {
-            let mut j = i + 1;
-            while j < self.empty.len() {
-                let (a,b) = (self.empty[i].clone(), self.empty[j].clone());
-                if a.supersets(&b) {
-                    self.empty.swap_remove(j);
-                } else if b.supersets(&a) {
-                    self.empty.swap_remove(i);
-                    j = i + 1;
-                } else {
-                    j += 1;
+        // Every maximal free rectangle of the grown bounds either lies within the new space, and
+        // so within one of the two strips added along the edges, or extends a free rectangle
+        // reaching the edges it crosses. Anything past the edges moves with them.
+        let mut grown = Vec::with_capacity(self.empty.len() + 2);
+        for free in self.empty.drain(..) {
+            let mut stretched = free.clone();
+            if grow_x && free.max.x >= old.max.x {
+                if free.min.x > old.max.x {
+                    stretched.min.x = max.x.clone() + (free.min.x.clone() - old.max.x.clone());
+                }
+                stretched.max.x = max.x.clone() + (free.max.x.clone() - old.max.x.clone());
+            }
+            if grow_y && free.max.y >= old.max.y {
+                if free.min.y > old.max.y {
+                    stretched.min.y = max.y.clone() + (free.min.y.clone() - old.max.y.clone());
                 }
+                stretched.max.y = max.y.clone() + (free.max.y.clone() - old.max.y.clone());
+            }
+            if stretched != free {
+                self.observer.split(&free, &[stretched.clone()]);
             }
+            grown.push(stretched);
+        }
+        if grow_x {
+            grown.push(Rect::new(Point::new(old.max.x.clone(), old.min.y.clone()), grown_extent.clone()));
+        }
+        if grow_y {
+            grown.push(Rect::new(Point::new(old.min.x.clone(), old.max.y.clone()), grown_extent));
+        }
 
-            i += 1;
+        self.bounds = Some(Rect::new(old.min, max));
+        for rect in grown.into_iter() {
+            self.insert_free(rect);
         }
+        self.truncate_free();
+        self.update_peak();
+        self.refresh_cache();
+        self.pack_queued();
     }
 
-    /// Maps a number of objects to rectangle sizes using `mapping` and continually packs the 
-    /// object with the best (by heuristic) possible packing. Fails if all elements cannot be 
-    /// packed. The returned `Vec` is an arbitrary permutation of the input with asscociated
-    /// positions
-    ///
-    /// Global packing is often better than normal packing, but is also slower.
-    pub fn pack_global<T,F>(&mut self, mut objects: Vec<T>, mut mapping: F)
-        -> Result<Vec<(T,(S,S))>, FailedPacking<T,S>>
-        where F:  for<'a>FnMut(&'a T) -> (S,S)
-    {
-        let mut packed = Vec::new();
+    /// The size a rectangle of size `size` takes up in the free rectangles, including padding,
+    /// row pitch and alignment
+    fn padded(&self, size: &Size<S>) -> Size<S> {
+        self.config.padded_size(size)
+    }
 
-        loop {
-            let min = objects.iter()
-                .enumerate()
-                .filter_map(|(index,x)| {
-                    let size = mapping(x);
-                    self.optimal(&size).map(move |(pos, h)| ((index, pos, size), h))
-                })
-                .min_cmp(|&(_,ref a), &(_,ref b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
-                .map(|(x,_)| x);
-
-            if let Some((index, (x,y), (xsize, ysize))) = min {
-                let element = objects.swap_remove(index);
-                let max = (x.clone() + xsize, y.clone() + ysize);
-                self.subtract_rect(&Rectangle::new((x.clone(),y.clone()), max));
-                packed.push((element, (x, y)));
-            } else {
-                return if objects.is_empty() {
-                    Ok(packed)
-                } else {
-                    Err(FailedPacking{partial_packed: packed, original: objects})
-                }
-            }
+    /// The number of orientations tried when packing a rectangle of size `size`
+    fn orientations(&self, size: &Size<S>) -> usize {
+        self.config.orientations(size)
+    }
+
+    /// Retrieves the best (by heuristic) placement of a rectangle in the free rectangles without
+    /// rotating it. Ties are broken by `Candidate::compare`.
+    fn fit(&self, rects: &[Rect<S>], size: &Size<S>) -> Option<Candidate<S>> {
+        self.candidates(rects, size).min_cmp(|a, b| a.compare(b))
+    }
+
+    /// Every placement of a rectangle in the free rectangles without rotating it.
+    fn candidates<'a>(&'a self, rects: &'a [Rect<S>], size: &Size<S>) -> impl Iterator<Item=Candidate<S>> + 'a {
+        self.config.candidates(self.placements, rects, size)
+    }
+
+    /// Retrieves the best placement of a rectangle as `fit` does, but remembers it for the next
+    /// time a rectangle of the same size is packed.
+    fn cached_fit(&mut self, size: &Size<S>) -> Option<Candidate<S>> {
+        if let Some(candidate) = self.cache.get(size) {
+            return candidate.clone();
+        }
+
+        self.stats.heuristic_evaluations += self.empty.len();
+        let candidate = self.fit(&self.empty, size);
+        if self.config.tie_seed.is_none() {
+            self.cache.insert(size.clone(), candidate.clone());
         }
+        candidate
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::{Rectangle, RectPacker};
+    /// Retrieves the best (by heuristic) placement of a rectangle, rotating it if that is allowed
+    /// and better. Ties are broken by `Candidate::compare` and then in favor of not rotating.
+    fn optimal(&mut self, size: &Size<S>) -> Option<Candidate<S>> {
+        let best = self.cached_fit(size);
+        if self.orientations(size) == 1 {
+            return best;
+        }
 
-    fn valid_pack(rectangles: &Vec<((u32,u32),(u32,u32))>) -> bool {
-        let as_rectangles = |&:| rectangles.iter().map(|&((x,y),(width,height))|
-            Rectangle::new((x,y), (x + width, x + height)));
+        let rotated = self.cached_fit(&Size::new(size.height.clone(), size.width.clone()));
+        Candidate::choose(best, rotated)
+    }
 
-        for (i,a) in as_rectangles().enumerate() {
-            for (j,b) in as_rectangles().enumerate() {
-                if i != j && a.intersects(&b) {
-                    return false;
+    /// Retrieves the best placement of a rectangle as `optimal` does, except that placements with
+    /// equal scores are first compared by how close they are to `near`. Nothing is cached.
+    fn optimal_near(&mut self, size: &Size<S>, near: &Point<S>) -> Option<Candidate<S>> {
+        let mut sizes = vec![size.clone()];
+        if self.orientations(size) == 2 {
+            sizes.push(Size::new(size.height.clone(), size.width.clone()));
+        }
+        self.stats.heuristic_evaluations += self.empty.len() * sizes.len();
+
+        let compare = |a: &S, b: &S| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+        let mut best: Option<(Candidate<S>, S)> = None;
+        for size in sizes.into_iter() {
+            for candidate in self.candidates(&self.empty, &size) {
+                let distance = manhattan(&candidate.position, near);
+                let better = match best {
+                    Some((ref best, ref closest)) => match compare(&candidate.score, &best.score) {
+                        Ordering::Equal => match compare(&distance, closest) {
+                            Ordering::Equal => candidate.compare(best) == Ordering::Less,
+                            ordering => ordering == Ordering::Less,
+                        },
+                        ordering => ordering == Ordering::Less,
+                    },
+                    None => true,
+                };
+
+                if better {
+                    best = Some((candidate, distance));
                 }
             }
         }
 
-        true
+        best.map(|(candidate, _)| candidate)
     }
 
-    #[test]
-    fn global_pack() {
-        let mut packer = RectPacker::new();
-        packer.add_free((0,0), (100,100));
-        let a = vec![(1,10), (9,9), (9,1)];
-        assert!(valid_pack(&packer.pack_global(a, |x| x.clone()).unwrap()));
+    /// Whether a rectangle is currently one of the free rectangles
+    fn is_free(&self, rect: &Rect<S>) -> bool {
+        let start = self.bound(|x| *x < rect.min.x);
+        self.empty[start..].iter()
+            .take_while(|x| x.min.x == rect.min.x)
+            .any(|x| x == rect)
+    }
+
+    /// Combines an earlier best placement with the best placement in the free rectangles added
+    /// since, in favor of the earlier one.
+    fn merge(earlier: Option<Candidate<S>>, added: Option<Candidate<S>>) -> Option<Candidate<S>> {
+        match (earlier, added) {
+            (Some(earlier), Some(added)) => match added.compare(&earlier) {
+                Ordering::Less => Some(added),
+                _ => Some(earlier),
+            },
+            (earlier, added) => earlier.or(added),
+        }
+    }
+
+    /// Brings the best placement of a rectangle of size `size` up to date after the free
+    /// rectangles have changed, given the free rectangles added since. The placement is only
+    /// searched for among all free rectangles if its free rectangle has been removed, or if ties
+    /// are broken by a seed. Returns the number of free rectangles scored.
+    fn refresh_fit(&self, fit: &mut Option<Candidate<S>>, size: &Size<S>, added: &[Rect<S>]) -> usize {
+        match fit.take() {
+            Some(ref earlier) if self.config.tie_seed.is_some() || !self.is_free(&earlier.free) => {
+                *fit = self.fit(&self.empty, size);
+                self.empty.len()
+            },
+            earlier => {
+                *fit = RectPacker::merge(earlier, self.fit(added, size));
+                added.len()
+            }
+        }
+    }
+
+    /// Brings the placements remembered by `cached_fit` up to date after the free rectangles have
+    /// changed. A remembered placement is still the best as long as its free rectangle remains,
+    /// except where a free rectangle added since is better. Returns the free rectangles added
+    /// since the last call.
+    fn refresh_cache(&mut self) -> Vec<Rect<S>> {
+        let (added, mut entries) = self.cache.take();
+        let added: Vec<Rect<S>> = added.into_iter().filter(|x| self.is_free(x)).collect();
+
+        entries.retain(|&(_, ref candidate)| candidate.as_ref().map_or(true, |x| self.is_free(&x.free)));
+        if !added.is_empty() {
+            for entry in entries.iter_mut() {
+                self.stats.heuristic_evaluations += self.refresh_fit(&mut entry.1, &entry.0, &added);
+            }
+        }
+
+        self.cache.restore(entries);
+        added
+    }
+
+    /// Brings the best placements of every size, unrotated and rotated, up to date as by
+    /// `refresh_fit`. Returns the number of free rectangles scored.
+    #[cfg(not(feature = "rayon"))]
+    fn refresh_fits(&self, sizes: &[(Size<S>, bool)], fits: &mut [Fits<S>], added: &[Rect<S>]) -> usize {
+        sizes.iter()
+            .zip(fits.iter_mut())
+            .fold(0, |sum, (&(ref size, rotatable), fit)| sum + self.refresh_both(size, rotatable, fit, added))
+    }
+
+    /// Brings the best placements of every size, unrotated and rotated, up to date as by
+    /// `refresh_fit`. Returns the number of free rectangles scored.
+    ///
+    /// The sizes are evaluated in parallel.
+    #[cfg(feature = "rayon")]
+    fn refresh_fits(&self, sizes: &[(Size<S>, bool)], fits: &mut [Fits<S>], added: &[Rect<S>]) -> usize where S: MaybeSync {
+        use rayon::prelude::*;
+
+        sizes.par_iter()
+            .zip(fits.par_iter_mut())
+            .map(|(&(ref size, rotatable), fit)| self.refresh_both(size, rotatable, fit, added))
+            .sum()
+    }
+
+    /// Brings the best placements of a size, unrotated and, if `rotatable`, rotated, up to date
+    /// as by `refresh_fit`. Returns the number of free rectangles scored.
+    fn refresh_both(&self, size: &Size<S>, rotatable: bool, fit: &mut Fits<S>, added: &[Rect<S>]) -> usize {
+        let mut scored = self.refresh_fit(&mut fit.0, size, added);
+        if rotatable && self.orientations(size) == 2 {
+            scored += self.refresh_fit(&mut fit.1, &Size::new(size.height.clone(), size.width.clone()), added);
+        }
+
+        scored
+    }
+
+    /// Removes a placement, including its padding, from the free rectangles. Returns the
+    /// rectangle that was packed and the free rectangles added by splitting.
+    fn place(&mut self, candidate: Candidate<S>) -> (Rect<S>, Vec<Rect<S>>) {
+        let padded = self.padded(&candidate.size);
+        self.subtract_rect(&Rect::with_size(candidate.position.clone(), padded));
+        let added = self.refresh_cache();
+
+        self.placements += 1;
+        let rect = Rect::with_size(candidate.position, candidate.size);
+        self.packed_area = Some(match self.packed_area.take() {
+            Some(area) => area + rect.area(),
+            None => rect.area(),
+        });
+        self.observer.place(&rect);
+        (rect, added)
+    }
+
+    /// Where a rectangle of size `size` is placed under the zero-size policy, if it is empty and
+    /// not packed like any other rectangle. It is then either not placed at all, or placed at the
+    /// minimum corner of the bounds.
+    fn place_empty(&self, size: &Size<S>) -> Option<Option<Rect<S>>> {
+        self.config.place_empty(self.bounds.as_ref(), size)
+    }
+
+    /// Counts an attempt to pack a rectangle of size `size` and applies the zero-size policy to
+    /// it, returning where it is placed if that decides it, as `place_empty` does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width or height is negative
+    fn admit(&mut self, size: &Size<S>) -> Option<Option<Rect<S>>> {
+        let placed = self.config.admit(self.bounds.as_ref(), size);
+        self.stats.packs_attempted += 1;
+        if placed.is_some() {
+            debug!("applied the zero-size policy to {:?}", size);
+        }
+        placed
+    }
+
+    /// Packs a rectangle into a free rectangle, so that it does not intersect any previously
+    /// packed rectangles. If a suitable position is found, the packed rectangle is returned,
+    /// with its width and height swapped if it was rotated. Otherwise `None` is returned.
+    ///
+    /// If several free rectangles are equally good by heuristic, the one yielding the lowest y
+    /// coordinate, and then the lowest x coordinate, is chosen.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width or height is negative
+    pub fn pack(&mut self, width: S, height: S) -> Option<Rect<S>> {
+        enter_span!("pack", free = self.empty.len());
+        let size = Size::new(width, height);
+        let placed = self.admit(&size);
+        self.record(Operation::Pack(size.clone()));
+        if let Some(placed) = placed {
+            return placed;
+        }
+
+        if let Some(candidate) = self.optimal(&size) {
+            debug!("packed {:?} at {:?}", candidate.size, candidate.position);
+            Some(self.place(candidate).0)
+        } else {
+            debug!("no free rectangle fits {:?}", size);
+            None
+        }
+    }
+
+    /// Packs a rectangle as `pack` does, except that among equally good placements by heuristic,
+    /// the one closest to `near` is chosen. This keeps related rectangles, such as the frames of
+    /// an animation, close together.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width or height is negative
+    pub fn pack_near(&mut self, width: S, height: S, near: &Rect<S>) -> Option<Rect<S>> {
+        enter_span!("pack_near", free = self.empty.len());
+        let size = Size::new(width, height);
+        let placed = self.admit(&size);
+        self.record(Operation::PackNear(size.clone(), near.clone()));
+        if let Some(placed) = placed {
+            return placed;
+        }
+
+        if let Some(candidate) = self.optimal_near(&size, &near.min) {
+            debug!("packed {:?} at {:?} near {:?}", candidate.size, candidate.position, near);
+            Some(self.place(candidate).0)
+        } else {
+            debug!("no free rectangle fits {:?}", size);
+            None
+        }
+    }
+
+    /// A fast check of whether rectangles of the given sizes could all be packed. This holds if
+    /// their total area, including padding, is no more than the free area, and each of them fits
+    /// within the widest and the tallest free rectangle.
+    ///
+    /// This is necessary but not sufficient: if it does not hold, packing all of the rectangles
+    /// is sure to fail, but if it does, packing them may still fail.
+    pub fn fits_by_area<Z>(&self, sizes: &[Z]) -> bool where Z: Clone + Into<Size<S>> {
+        let padded: Vec<Size<S>> = sizes.iter().map(|x| self.padded(&x.clone().into())).collect();
+        if padded.is_empty() {
+            return true;
+        } else if self.empty.is_empty() {
+            return false;
+        }
+
+        // Free rectangles may overlap, so both their total area and the area of their bounds are
+        // upper bounds on the free area.
+        let mut free_area = sum(self.empty.iter().map(|x| x.area())).unwrap();
+        if let Some(ref bounds) = self.bounds {
+            if bounds.area() < free_area {
+                free_area = bounds.area();
+            }
+        }
+
+        if sum(padded.iter().map(|x| x.width.clone() * x.height.clone())).unwrap() > free_area {
+            return false;
+        }
+
+        let compare = |a: &S, b: &S| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+        let widest = self.empty.iter().map(|x| x.width()).max_cmp(|a, b| compare(a, b)).unwrap();
+        let tallest = self.empty.iter().map(|x| x.height()).max_cmp(|a, b| compare(a, b)).unwrap();
+        padded.iter().all(|size| {
+            (size.width <= widest && size.height <= tallest) ||
+            (self.config.allow_rotation && size.height <= widest && size.width <= tallest)
+        })
+    }
+
+    /// Packs a rectangle as `pack`, but if it does not fit, queues it to be packed once room
+    /// appears through `add_free`. Returns the packed rectangle, or otherwise a ticket identifying
+    /// the queued rectangle in `queued` and `drain_queued`.
+    pub fn pack_or_queue(&mut self, width: S, height: S) -> Result<Rect<S>, usize> {
+        match self.pack(width.clone(), height.clone()) {
+            Some(rect) => Ok(rect),
+            None => {
+                let ticket = self.next_ticket;
+                self.next_ticket += 1;
+                self.queue.push((ticket, Size::new(width, height)));
+                Err(ticket)
+            }
+        }
+    }
+
+    /// The rectangles queued by `pack_or_queue` that have not yet been packed, with their tickets,
+    /// in the order they were queued
+    #[inline]
+    pub fn queued(&self) -> &[(usize, Size<S>)] {
+        &self.queue
+    }
+
+    /// Returns the queued rectangles that have been packed since the last call, with their
+    /// tickets, in the order they were packed.
+    pub fn drain_queued(&mut self) -> Vec<(usize, Rect<S>)> {
+        ::std::mem::replace(&mut self.dequeued, Vec::new())
+    }
+
+    /// Attempts to pack every queued rectangle in queue order, keeping those that do not fit.
+    fn pack_queued(&mut self) {
+        let queue = ::std::mem::replace(&mut self.queue, Vec::new());
+        for (ticket, Size{width, height}) in queue.into_iter() {
+            match self.pack(width.clone(), height.clone()) {
+                Some(rect) => {
+                    debug!("packed queued rectangle {}", ticket);
+                    self.dequeued.push((ticket, rect));
+                },
+                None => self.queue.push((ticket, Size::new(width, height))),
+            }
+        }
+    }
+
+    /// Packs a number of rectangles in the given order, as if by calling `pack` on each of them.
+    /// The returned `Vec` contains each packed rectangle at the same index as its size, or `None`
+    /// if it could not be packed.
+    pub fn pack_many<Z>(&mut self, sizes: &[Z]) -> Vec<Option<Rect<S>>> where Z: Clone + Into<Size<S>> {
+        let mut positions = Vec::with_capacity(sizes.len());
+
+        for size in sizes.iter() {
+            let Size{width, height} = size.clone().into();
+            positions.push(self.pack(width, height));
+        }
+
+        positions
+    }
+
+    /// Packs the frames of an animation in order, each as close as `pack_near` allows to the last
+    /// frame that was packed. The returned `Vec` is as for `pack_many`.
+    pub fn pack_frames<Z>(&mut self, frames: &[Z]) -> Vec<Option<Rect<S>>> where Z: Clone + Into<Size<S>> {
+        let mut positions = Vec::with_capacity(frames.len());
+        let mut last: Option<Rect<S>> = None;
+
+        for frame in frames.iter() {
+            let Size{width, height} = frame.clone().into();
+            let packed = match last {
+                Some(ref last) => self.pack_near(width, height, last),
+                None => self.pack(width, height),
+            };
+
+            if packed.is_some() {
+                last = packed.clone();
+            }
+            positions.push(packed);
+        }
+
+        positions
+    }
+
+    /// Maps a number of objects to rectangle sizes using `mapping`, sorts them by `order` and packs
+    /// them one at a time in that order. Objects that do not fit are skipped, so that the
+    /// remaining objects may still be packed, but the packing fails as a whole. The returned
+    /// `Vec` is in packing order.
+    pub fn pack_sorted<T,F,Z>(&mut self, objects: Vec<T>, order: SortOrder, mut mapping: F)
+        -> Result<Vec<(T,Rect<S>)>, FailedPacking<T,S>>
+        where F: for<'a>FnMut(&'a T) -> Z, Z: Into<Size<S>>
+    {
+        let mut sorted: Vec<_> = objects.into_iter()
+            .map(|x| {
+                let size = mapping(&x).into();
+                (order.key(&size), size, x)
+            })
+            .collect();
+        sorted.sort_by(|&(ref a, _, _), &(ref b, _, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+        let (packed, failed) = self.pack_in_order(sorted.into_iter().map(|(_, size, x)| (size, x)));
+        if failed.is_empty() {
+            Ok(packed)
+        } else {
+            Err(FailedPacking{partial_packed: packed, original: failed, interrupted: false})
+        }
+    }
+
+    /// Packs as many objects as possible when not all of them fit, maximizing the total value
+    /// given to the packed objects by `value`. Objects are mapped to rectangle sizes using
+    /// `mapping` and packed one at a time by decreasing value per area, skipping those that do not
+    /// fit. Objects with an empty size take no area and are packed first. Returns the packed
+    /// objects in packing order and the objects left over.
+    ///
+    /// This is a greedy approximation: the packed value is not guaranteed to be the highest
+    /// possible.
+    pub fn pack_by_value<T,F,Z,V>(&mut self, objects: Vec<T>, mut mapping: F, mut value: V)
+        -> (Vec<(T,Rect<S>)>, Vec<T>)
+        where F: for<'a>FnMut(&'a T) -> Z, Z: Into<Size<S>>, V: for<'a>FnMut(&'a T) -> f64, S: Into<f64>
+    {
+        let mut sorted: Vec<_> = objects.into_iter()
+            .map(|x| {
+                let size: Size<S> = mapping(&x).into();
+                let area: f64 = (size.width.clone() * size.height.clone()).into();
+                let density = if area == 0.0 { ::std::f64::INFINITY } else { value(&x) / area };
+                (density, size, x)
+            })
+            .collect();
+        sorted.sort_by(|&(a, _, _), &(b, _, _)| b.total_cmp(&a));
+
+        self.pack_in_order(sorted.into_iter().map(|(_, size, x)| (size, x)))
+    }
+
+    /// Packs as many objects as possible when not all of them fit, maximizing the number of
+    /// objects packed. Objects are mapped to rectangle sizes using `mapping`, and the largest
+    /// number of the smallest objects that can be packed globally is found by a binary search.
+    /// The remaining objects are then packed one at a time where they still fit. Returns the
+    /// packed objects and the objects left over.
+    pub fn pack_most<T,F,Z>(&mut self, objects: Vec<T>, mut mapping: F) -> (Vec<(T,Rect<S>)>, Vec<T>)
+        where F: for<'a>FnMut(&'a T) -> Z, Z: Into<Size<S>>, S: MaybeSync
+    {
+        let mut sorted: Vec<_> = objects.into_iter()
+            .map(|x| {
+                let size: Size<S> = mapping(&x).into();
+                (size.width.clone() * size.height.clone(), size, x)
+            })
+            .collect();
+        sorted.sort_by(|&(ref a, _, _), &(ref b, _, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let sizes: Vec<Size<S>> = sorted.iter().map(|&(_, ref size, _)| size.clone()).collect();
+
+        // Packing fewer of the smallest objects is rarely harder, so the count is searched for by
+        // packing copies of the packer. The count found is then packed by this packer itself, so
+        // that its observer and recording see the placements.
+        let (mut low, mut high) = (0, sizes.len());
+        while low < high {
+            let mid = (low + high + 1) / 2;
+            let mut packer = self.clone();
+            packer.recording = None;
+            match packer.pack_global(0..mid, |&index| sizes[index].clone()) {
+                Ok(_) => low = mid,
+                Err(_) => high = mid - 1,
+            }
+        }
+        debug!("packed the {} smallest of {} objects globally", low, sizes.len());
+
+        // Packing is deterministic, so this succeeds as it did for the copy. Should it still fail,
+        // the objects it did not pack are packed one at a time with the rest.
+        let placements = match low {
+            0 => Vec::new(),
+            _ => match self.pack_global(0..low, |&index| sizes[index].clone()) {
+                Ok(packed) => packed.into_vec(),
+                Err(failed) => failed.into_parts().0,
+            },
+        };
+
+        let mut objects: Vec<_> = sorted.into_iter().map(|(_, _, x)| Some(x)).collect();
+        let mut packed: Vec<_> = placements.into_iter()
+            .map(|(index, rect)| (objects[index].take().unwrap(), rect))
+            .collect();
+        let rest = objects.into_iter()
+            .zip(sizes.into_iter())
+            .filter_map(|(x, size)| x.map(|x| (size, x)));
+
+        let (late, leftover) = self.pack_in_order(rest);
+        packed.extend(late.into_iter());
+        (packed, leftover)
+    }
+
+    /// Packs objects of the given sizes one at a time in the given order, skipping those that do
+    /// not fit. Returns the packed objects and the skipped objects, both in order.
+    fn pack_in_order<T,I>(&mut self, objects: I) -> (Vec<(T,Rect<S>)>, Vec<T>) where I: Iterator<Item=(Size<S>, T)> {
+        let mut packed = Vec::new();
+        let mut failed = Vec::new();
+        for (Size{width, height}, x) in objects {
+            match self.pack(width, height) {
+                Some(rect) => packed.push((x, rect)),
+                None => failed.push(x),
+            }
+        }
+
+        (packed, failed)
+    }
+
+    /// Returns the number of free rectangles whose minimum x coordinate satisfies `before`. As the
+    /// free list is sorted by this coordinate, these are the first rectangles of the list,
+    /// provided `before` holds for every coordinate less than one it holds for.
+    fn bound<F>(&self, mut before: F) -> usize where F: FnMut(&S) -> bool {
+        let (mut low, mut high) = (0, self.empty.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            if before(&self.empty[mid].min.x) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        low
+    }
+
+    /// Inserts a rectangle into the free list in sorted order, unless another free rectangle
+    /// already contains it. Any free rectangle it contains is removed, so that no free rectangle
+    /// is ever a subset of another.
+    fn insert_free(&mut self, rect: Rect<S>) {
+        self.stats.prune_passes += 1;
+
+        // Only rectangles starting no later than `rect` can contain it
+        let end = self.bound(|x| *x <= rect.min.x);
+        if self.empty[..end].iter().any(|free| free.supersets(&rect)) {
+            trace!("pruned free rectangle {:?}..{:?}", rect.min, rect.max);
+            self.stats.pruned_rects += 1;
+            self.observer.prune(&rect);
+            return;
+        }
+
+        // Only rectangles starting within `rect` can be contained in it
+        let start = self.bound(|x| *x < rect.min.x);
+        let end = self.bound(|x| *x <= rect.max.x);
+        let mut kept = start;
+        for index in start..end {
+            if !rect.supersets(&self.empty[index]) {
+                self.empty.swap(kept, index);
+                kept += 1;
+            } else {
+                self.observer.prune(&self.empty[index]);
+            }
+        }
+        self.empty.drain(kept..end);
+        self.stats.pruned_rects += end - kept;
+        if end > kept {
+            trace!("pruned {} free rectangles contained in {:?}..{:?}", end - kept, rect.min, rect.max);
+        }
+
+        self.cache.added(rect.clone());
+        self.empty.insert(start, rect);
+    }
+
+    /// Removes a rectangle from the list of free rectangles, so that no remaining free rectangle
+    /// intersects with this rectangle
+    ///
+    /// The free rectangles are kept sorted by x rather than in a spatial index. A grid needs
+    /// coordinates that map to cells, which `S` does not have, and a tree would not keep them in
+    /// the slice `free_rects` returns, in the order ties are broken by. A window bounded by the
+    /// widest free rectangle would need arithmetic that can overflow or round. The scan is thus
+    /// linear in the number of free rectangles starting before `sub` ends.
+    fn subtract_rect(&mut self, sub: &Rect<S>) {
+        enter_span!("subtract_rect", free = self.empty.len());
+
+        // The free list is sorted by the minimum x coordinate, so only the rectangles starting
+        // before `sub` ends can intersect it. Those that do are split into the parts not covered
+        // by `sub`, while the rest are moved to the front in their original order.
+        let end = self.bound(|x| *x < sub.max.x);
+        let mut split = Vec::new();
+        let mut kept = 0;
+        for index in 0..end {
+            if self.empty[index].intersects(sub) {
+                let free = &self.empty[index];
+                let first = split.len();
+                let mut push = |min, max| split.push(Rect::new(min,max));
+
+                if sub.min.x > free.min.x {
+                    push(free.min.clone(), Point::new(sub.min.x.clone(), free.max.y.clone()));
+                }
+
+                if sub.min.y > free.min.y {
+                    push(free.min.clone(), Point::new(free.max.x.clone(), sub.min.y.clone()));
+                }
+
+                if sub.max.x < free.max.x {
+                    push(Point::new(sub.max.x.clone(), free.min.y.clone()), free.max.clone());
+                }
+
+                if sub.max.y < free.max.y {
+                    push(Point::new(free.min.x.clone(), sub.max.y.clone()), free.max.clone());
+                }
+
+                self.observer.split(free, &split[first..]);
+            } else {
+                self.empty.swap(kept, index);
+                kept += 1;
+            }
+        }
+        self.empty.drain(kept..end);
+        trace!("split {} free rectangles intersecting {:?}..{:?} into {}",
+               end - kept, sub.min, sub.max, split.len());
+
+        // The split rectangles are subsets of removed rectangles, so they cannot contain any
+        // remaining free rectangle, but they may be contained in one or in each other.
+        for rect in split.into_iter() {
+            self.insert_free(rect);
+        }
+
+        self.truncate_free();
+        self.update_peak();
+    }
+
+    /// Maps a number of objects to rectangle sizes using `mapping` and continually packs the 
+    /// object with the best (by heuristic) possible packing. Fails if all elements cannot be 
+    /// packed. The returned `PackedResult` is an arbitrary permutation of the input with asscociated
+    /// rectangles
+    ///
+    /// `objects` can be any collection or iterator, such as a `Vec`, a slice iterator or a map.
+    ///
+    /// Global packing is often better than normal packing, but is also slower. To fail fast on
+    /// objects that cannot all fit, check them with `fits_by_area` first.
+    ///
+    /// With the `rayon` feature enabled, the objects are evaluated in parallel on every step. With
+    /// rotation allowed, every object is evaluated in both orientations, so an object may be
+    /// chosen for the best placement of its rotated size.
+    ///
+    /// If several objects are equally good by heuristic, ties are broken as for `pack` and then
+    /// in favor of the object appearing first in `objects`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width or height of an object is negative
+    pub fn pack_global<T,O,F,Z>(&mut self, objects: O, mapping: F)
+        -> Result<PackedResult<T,S>, FailedPacking<T,S>>
+        where O: IntoIterator<Item=T>, F:  for<'a>FnMut(&'a T) -> Z, Z: Into<Size<S>>, S: MaybeSync
+    {
+        self.pack_global_with_progress(objects, mapping, |_| ())
+    }
+
+    /// Packs objects as `pack_global`, but calls `progress` after every placement with the
+    /// progress made so far, e.g. to drive a progress bar during long packings.
+    pub fn pack_global_with_progress<T,O,F,Z,P>(&mut self, objects: O, mapping: F, progress: P)
+        -> Result<PackedResult<T,S>, FailedPacking<T,S>>
+        where O: IntoIterator<Item=T>, F:  for<'a>FnMut(&'a T) -> Z, Z: Into<Size<S>>, P: FnMut(&Progress<S>), S: MaybeSync
+    {
+        self.pack_global_impl(objects.into_iter().collect(), mapping, progress, || false)
+    }
+
+    /// Packs objects as `pack_global`, but stops before the next placement once `interrupt` is
+    /// triggered, e.g. when a cancellation flag is set or a deadline has passed. An interrupted
+    /// packing fails with the objects packed so far, which can be retrieved from the error.
+    pub fn pack_global_interruptible<T,O,F,Z,I>(&mut self, objects: O, mapping: F, mut interrupt: I)
+        -> Result<PackedResult<T,S>, FailedPacking<T,S>>
+        where O: IntoIterator<Item=T>, F:  for<'a>FnMut(&'a T) -> Z, Z: Into<Size<S>>, I: Interrupt, S: MaybeSync
+    {
+        self.pack_global_impl(objects.into_iter().collect(), mapping, |_| (), || interrupt.interrupted())
+    }
+
+    /// The global packing loop shared by all variants of `pack_global`. `interrupted` is checked
+    /// before every placement.
+    fn pack_global_impl<T,F,Z,P,I>(&mut self, objects: Vec<T>, mut mapping: F, progress: P, interrupted: I)
+        -> Result<PackedResult<T,S>, FailedPacking<T,S>>
+        where F:  for<'a>FnMut(&'a T) -> Z, Z: Into<Size<S>>, P: FnMut(&Progress<S>), I: FnMut() -> bool,
+              S: MaybeSync
+    {
+        let sizes: Vec<Size<S>> = objects.iter().map(|x| mapping(x).into()).collect();
+        let recorded = self.recording.as_ref().map(|_| sizes.clone());
+
+        let oriented = sizes.iter().map(|x| (x.clone(), true)).collect();
+        let result = self.pack_global_sizes(objects, oriented, progress, interrupted);
+        if let Some(sizes) = recorded {
+            let placements = match result {
+                Ok(ref packed) => packed.len(),
+                Err(ref failed) => failed.packed().len(),
+            };
+            self.record(Operation::PackGlobal(sizes, placements));
+        }
+
+        result.map(|packed| PackedResult::new(packed, self.bounds.clone()))
+    }
+
+    /// Globally packs objects of the given sizes, as `pack_global_impl`. Each size is paired with
+    /// whether the object may be rotated, if rotation is allowed at all.
+    fn pack_global_sizes<T,P,I>(&mut self, objects: Vec<T>, sizes: Vec<(Size<S>, bool)>, progress: P, interrupted: I)
+        -> Result<Vec<(T,Rect<S>)>, FailedPacking<T,S>>
+        where P: FnMut(&Progress<S>), I: FnMut() -> bool, S: MaybeSync
+    {
+        let mut packed = Vec::new();
+        match self.pack_global_streamed(objects, sizes, &mut |object, rect| packed.push((object, rect)), progress, interrupted) {
+            Ok(()) => Ok(packed),
+            Err(failed) => Err(FailedPacking{partial_packed: packed, ..failed}),
+        }
+    }
+
+    /// Globally packs objects of the given sizes as `pack_global_sizes`, but passes every packed
+    /// object to `sink` as soon as it is packed. A failed packing holds only the objects that
+    /// were not packed.
+    fn pack_global_streamed<T,K,P,I>(&mut self, objects: Vec<T>, sizes: Vec<(Size<S>, bool)>, sink: &mut K,
+                                     mut progress: P, mut interrupted: I) -> Result<(), FailedPacking<T,S>>
+        where K: FnMut(T, Rect<S>), P: FnMut(&Progress<S>), I: FnMut() -> bool, S: MaybeSync
+    {
+        enter_span!("pack_global", objects = objects.len(), free = self.empty.len());
+        if sizes.iter().any(|x| x.0.is_negative()) {
+            panic!("width and height cannot be negative");
+        }
+
+        if self.config.zero_size != ZeroSizePolicy::Pack && sizes.iter().any(|x| x.0.is_empty()) {
+            return self.pack_global_empty(objects, sizes, sink, progress, interrupted);
+        }
+
+        let mut placed = 0;
+        let mut packed_area: Option<S> = None;
+        self.stats.packs_attempted += objects.len();
+
+        // Objects of identical sizes are placed equally well, so the heuristic is only evaluated
+        // once per distinct size. Each group holds the indices of its remaining objects in input
+        // order, and the groups are kept ordered by their first remaining object, so that ties
+        // are still broken in favor of the object appearing first.
+        let mut unique: Vec<(Size<S>, bool)> = Vec::new();
+        let mut groups: Vec<VecDeque<usize>> = Vec::new();
+        for (index, size) in sizes.into_iter().enumerate() {
+            match unique.iter().position(|x| *x == size) {
+                Some(group) => groups[group].push_back(index),
+                None => {
+                    unique.push(size);
+                    groups.push(Some(index).into_iter().collect());
+                }
+            }
+        }
+
+        let mut remaining = objects.len();
+        let mut objects: Vec<Option<T>> = objects.into_iter().map(Some).collect();
+
+        // The best placement of every group is kept between steps and only recomputed where the
+        // free rectangles changed. At first, every free rectangle is new.
+        let mut fits: Vec<Fits<S>> = unique.iter().map(|_| (None, None)).collect();
+        let mut added = self.empty.clone();
+
+        loop {
+            if remaining > 0 && interrupted() {
+                debug!("global packing interrupted with {} objects packed and {} left", placed, remaining);
+                let original = objects.into_iter().filter_map(|x| x).collect();
+                return Err(FailedPacking{partial_packed: Vec::new(), original: original, interrupted: true});
+            }
+
+            self.stats.heuristic_evaluations += self.refresh_fits(&unique, &mut fits, &added);
+
+            let best = fits.iter()
+                .enumerate()
+                .filter_map(|(group, &(ref fit, ref rotated))|
+                    Candidate::choose(fit.clone(), rotated.clone()).map(|candidate| (group, candidate)))
+                .min_cmp(|&(_, ref a), &(_, ref b)| a.compare(b));
+
+            if let Some((group, candidate)) = best {
+                let size = unique.remove(group);
+                let fit = fits.remove(group);
+                let mut members = groups.remove(group);
+                let element = objects[members.pop_front().unwrap()].take().unwrap();
+                remaining -= 1;
+
+                if let Some(&first) = members.front() {
+                    let position = groups.iter().position(|x| x[0] > first).unwrap_or(groups.len());
+                    unique.insert(position, size);
+                    fits.insert(position, fit);
+                    groups.insert(position, members);
+                }
+
+                let (rect, split) = self.place(candidate);
+                added = split;
+                packed_area = Some(match packed_area {
+                    Some(area) => area + rect.area(),
+                    None => rect.area(),
+                });
+                placed += 1;
+                sink(element, rect);
+                progress(&Progress{
+                    placed: placed,
+                    remaining: remaining,
+                    packed_area: packed_area.clone().unwrap(),
+                    bin_area: self.bounds.as_ref().unwrap().area(),
+                });
+            } else {
+                debug!("globally packed {} objects with {} left", placed, remaining);
+                return if remaining == 0 {
+                    Ok(())
+                } else {
+                    let original = objects.into_iter().filter_map(|x| x).collect();
+                    Err(FailedPacking{partial_packed: Vec::new(), original: original, interrupted: false})
+                }
+            }
+        }
+    }
+
+    /// Globally packs objects of the given sizes as `pack_global_streamed`, when some of them are
+    /// empty and not packed like any other under the zero-size policy. The other objects are
+    /// packed first, unless the empty ones are rejected.
+    fn pack_global_empty<T,K,P,I>(&mut self, objects: Vec<T>, sizes: Vec<(Size<S>, bool)>, sink: &mut K,
+                                  progress: P, interrupted: I) -> Result<(), FailedPacking<T,S>>
+        where K: FnMut(T, Rect<S>), P: FnMut(&Progress<S>), I: FnMut() -> bool, S: MaybeSync
+    {
+        if self.config.zero_size == ZeroSizePolicy::Reject {
+            debug!("rejected {} objects including empty ones", objects.len());
+            self.stats.packs_attempted += objects.len();
+            return Err(FailedPacking{partial_packed: Vec::new(), original: objects, interrupted: false});
+        }
+
+        let mut empty = Vec::new();
+        let (mut rest, mut rest_sizes) = (Vec::new(), Vec::new());
+        for (object, size) in objects.into_iter().zip(sizes.into_iter()) {
+            match self.place_empty(&size.0) {
+                Some(placed) => empty.push((object, placed)),
+                None => {
+                    rest.push(object);
+                    rest_sizes.push(size);
+                }
+            }
+        }
+        self.stats.packs_attempted += empty.len();
+
+        let (mut left, interrupted) = match self.pack_global_streamed(rest, rest_sizes, sink, progress, interrupted) {
+            Ok(()) => (Vec::new(), false),
+            Err(failed) => (failed.original, failed.interrupted),
+        };
+        for (object, placed) in empty.into_iter() {
+            match placed {
+                Some(rect) => sink(object, rect),
+                None => left.push(object),
+            }
+        }
+
+        if left.is_empty() {
+            Ok(())
+        } else {
+            Err(FailedPacking{partial_packed: Vec::new(), original: left, interrupted: interrupted})
+        }
+    }
+
+    /// Globally packs objects as `pack_global`, using the size of each object itself. With the
+    /// `image` feature enabled, this packs images directly.
+    pub fn pack_global_sized<T,O>(&mut self, objects: O) -> Result<PackedResult<T,S>, FailedPacking<T,S>>
+        where O: IntoIterator<Item=T>, T: SizeSource<S>, S: MaybeSync
+    {
+        self.pack_global(objects, |x| x.rect_size())
+    }
+
+    /// Globally packs objects as `pack_global` with every configuration in `configs` at once, each
+    /// on its own thread and on its own copy of the packer. As soon as one configuration packs
+    /// every object, the others are interrupted. Returns the index of that configuration along
+    /// with its packing, and this packer takes on the state of the copy that packed them. Its
+    /// observer is kept, but does not see the placements made by the copies.
+    ///
+    /// If several configurations succeed, which one is returned may vary between runs. If none
+    /// succeed, the failure packing the most objects is returned, preferring the first
+    /// configuration among equals.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `configs` is empty
+    pub fn solve_portfolio<T,F,Z>(&mut self, configs: &[PackerConfig<S>], objects: Vec<T>, mapping: F)
+        -> Result<(usize, PackedResult<T,S>), FailedPacking<T,S>>
+        where T: Clone + Send, F: Fn(&T) -> Z + Sync, Z: Into<Size<S>>, S: Send + Sync + MaybeSync
+    {
+        assert!(!configs.is_empty(), "a portfolio needs at least one configuration");
+        enter_span!("solve_portfolio", configs = configs.len(), objects = objects.len());
+
+        // Copies of the packer have no observer, so it is kept aside and handed to the copy
+        // taking the place of this packer.
+        let observer = self.take_observer();
+        let cancel = AtomicBool::new(false);
+        let results: Vec<_> = thread::scope(|scope| {
+            let handles: Vec<_> = configs.iter().map(|config| {
+                let mut packer = self.clone();
+                packer.set_config(config.clone());
+                let (objects, mapping, cancel) = (objects.clone(), &mapping, &cancel);
+
+                scope.spawn(move || {
+                    let result = packer.pack_global_interruptible(objects, |x| mapping(x), cancel);
+                    if result.is_ok() {
+                        cancel.store(true, AtomicOrdering::Relaxed);
+                    }
+                    (packer, result)
+                })
+            }).collect();
+
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+        });
+
+        let mut best: Option<(usize, RectPacker<S>, Result<_, FailedPacking<T,S>>)> = None;
+        for (index, (packer, result)) in results.into_iter().enumerate() {
+            let better = match (&best, &result) {
+                (&None, _) => true,
+                (&Some((_, _, Ok(_))), _) => false,
+                (&Some((_, _, Err(_))), &Ok(_)) => true,
+                (&Some((_, _, Err(ref a))), &Err(ref b)) => b.packed().len() > a.packed().len(),
+            };
+
+            if better {
+                best = Some((index, packer, result));
+            }
+        }
+
+        let (index, packer, result) = best.unwrap();
+        debug!("portfolio solved by configuration {} of {}: {}", index, configs.len(), result.is_ok());
+        *self = packer;
+        self.observer.set(observer);
+        result.map(|packed| (index, packed))
+    }
+
+    /// Globally packs objects as `pack_global`, partitioned by a tag that `mapping` returns along
+    /// with each size. The objects of every tag are packed into their own copy of this packer, so
+    /// objects with different tags never share free space. Returns the packer and the result of
+    /// packing for every tag.
+    pub fn pack_global_by_tag<T,K,F,Z>(&self, objects: Vec<T>, mut mapping: F)
+        -> HashMap<K, (RectPacker<S>, Result<PackedResult<T,S>, FailedPacking<T,S>>)>
+        where K: Hash + Eq, F: FnMut(&T) -> (K, Z), Z: Into<Size<S>>, S: MaybeSync
+    {
+        let mut partitions: HashMap<K, (Vec<T>, Vec<Size<S>>)> = HashMap::new();
+        for object in objects {
+            let (tag, size) = mapping(&object);
+            let partition = partitions.entry(tag).or_insert_with(|| (Vec::new(), Vec::new()));
+            partition.0.push(object);
+            partition.1.push(size.into());
+        }
+
+        partitions.into_iter().map(|(tag, (objects, sizes))| {
+            let mut packer = self.clone();
+            // The objects are mapped in order, so their sizes can be handed out in order too.
+            let mut sizes = sizes.into_iter();
+            let result = packer.pack_global_impl(objects, |_| sizes.next().unwrap(), |_| (), || false);
+            (tag, (packer, result))
+        }).collect()
+    }
+
+    /// Globally packs a map of rectangle sizes and returns every packed rectangle under the same
+    /// key its size was given with. Fails if all elements cannot be packed.
+    pub fn pack_into_map<K,Z>(&mut self, items: HashMap<K,Z>)
+        -> Result<HashMap<K,Rect<S>>, FailedPacking<(K,Z),S>>
+        where K: Hash + Eq, Z: Clone + Into<Size<S>>, S: MaybeSync
+    {
+        self.pack_global(items, |&(_, ref size)| size.clone())
+            .map(|packed| packed.into_iter().map(|((key, _), rect)| (key, rect)).collect())
+    }
+}
+
+/// An iterator that packs every element of another iterator as it is yielded.
+///
+/// This is created by `PackIteratorExt::pack_with`.
+pub struct PackWith<'a, I, F, S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug + 'a {
+    iter: I,
+    packer: &'a mut RectPacker<S>,
+    mapping: F,
+}
+
+impl<'a, I, F, Z, S> Iterator for PackWith<'a, I, F, S> where
+    I: Iterator,
+    F: FnMut(&I::Item) -> Z,
+    Z: Into<Size<S>>,
+    S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug
+{
+    type Item = (I::Item, Option<Rect<S>>);
+
+    fn next(&mut self) -> Option<(I::Item, Option<Rect<S>>)> {
+        self.iter.next().map(|item| {
+            let Size{width, height} = (self.mapping)(&item).into();
+            let rect = self.packer.pack(width, height);
+            (item, rect)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Extends iterators with the ability to pack their elements lazily.
+pub trait PackIteratorExt: Iterator + Sized {
+    /// Maps every element to a rectangle size using `mapping` and packs it into `packer` when it
+    /// is yielded, as if by calling `pack`. Each element is yielded together with its packed
+    /// rectangle, or `None` if it could not be packed.
+    fn pack_with<'a, S, F, Z>(self, packer: &'a mut RectPacker<S>, mapping: F) -> PackWith<'a, Self, F, S> where
+        F: FnMut(&Self::Item) -> Z,
+        Z: Into<Size<S>>,
+        S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug
+    {
+        PackWith{iter: self, packer: packer, mapping: mapping}
+    }
+}
+
+impl<I> PackIteratorExt for I where I: Iterator {}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use std::sync::atomic::AtomicBool;
+    use std::time::Instant;
+    use super::{Point, Rect, Size, SizeSource, RectPacker, PackIteratorExt, SortOrder, Heuristic,
+                PackerConfig, ZeroSizePolicy, Observer, Operation, estimate_bin_size};
+    use generate::InstanceGenerator;
+
+    fn valid_pack<T>(rectangles: &[(T,Rect<u32>)]) -> bool {
+        for (i,&(_, ref a)) in rectangles.iter().enumerate() {
+            for (j,&(_, ref b)) in rectangles.iter().enumerate() {
+                if i != j && a.intersects(b) {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    #[test]
+    fn global_pack() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (100,100));
+        let a = vec![(1,10), (9,9), (9,1)];
+        assert!(valid_pack(&packer.pack_global(a, |x| x.clone()).unwrap()));
     }
 
     #[test]
@@ -306,8 +2054,661 @@ mod test {
         let mut packer = RectPacker::new();
         packer.add_free((0,0), (100,100));
 
-        println!("{:?}", packer.pack(1,10).unwrap());
-        println!("{:?}", packer.pack(9,9).unwrap());
-        println!("{:?}", packer.pack(9,1).unwrap());
+        assert_eq!(packer.pack(1,10), Some(Rect::new((0,0), (1,10))));
+        assert_eq!(packer.pack(9,9), Some(Rect::new((0,10), (9,19))));
+        assert_eq!(packer.pack(9,1), Some(Rect::new((1,0), (10,1))));
+    }
+
+    #[test]
+    fn map_pack() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (100,100));
+
+        let mut sprites = HashMap::new();
+        sprites.insert("player", (10,20));
+        sprites.insert("enemy", (20,10));
+
+        let packed = packer.pack_into_map(sprites).unwrap();
+        assert_eq!(packed.len(), 2);
+        assert!(packed.contains_key("player") && packed.contains_key("enemy"));
+    }
+
+    #[test]
+    fn many_pack() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (10,10));
+
+        let packed = packer.pack_many(&[(10,5), (20,1), (10,5), (1,1)]);
+        assert!(packed[0].is_some() && packed[2].is_some());
+        assert!(packed[1].is_none() && packed[3].is_none());
+    }
+
+    #[test]
+    fn iterator_pack() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (10,10));
+
+        let sizes = vec![(10,5), (20,1), (10,5)];
+        let packed: Vec<_> = sizes.iter().pack_with(&mut packer, |&&size| size).collect();
+        assert_eq!(packed.len(), 3);
+        assert!(packed[0].1.is_some() && packed[1].1.is_none() && packed[2].1.is_some());
+    }
+
+    #[test]
+    fn sorted_pack() {
+        let orders = [SortOrder::AreaDesc, SortOrder::ShortSideDesc,
+                      SortOrder::LongSideDesc, SortOrder::PerimeterDesc];
+
+        for &order in orders.iter() {
+            let mut packer = RectPacker::new();
+            packer.add_free((0,0), (10,10));
+
+            let packed = packer.pack_sorted(vec![(2,2), (10,6), (5,2), (5,2)], order, |&x| x).unwrap();
+            assert_eq!(packed[0].0, (10,6));
+            assert!(valid_pack(&packed));
+        }
+    }
+
+    #[test]
+    fn value_pack() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (10,10));
+
+        let items = vec![("large", (10,10), 50.0), ("small", (5,5), 20.0), ("wide", (10,5), 40.0)];
+        let (packed, leftover) = packer.pack_by_value(items, |&(_, size, _)| size, |&(_, _, value)| value);
+
+        let names: Vec<_> = packed.iter().map(|&((name, _, _), _)| name).collect();
+        assert_eq!(names, vec!["small", "wide"]);
+        assert_eq!(leftover.len(), 1);
+        assert!(valid_pack(&packed));
+    }
+
+    #[test]
+    fn value_pack_empty() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (10,10));
+
+        let items = vec![("large", (10,10), 50.0), ("small", (5,5), 20.0), ("empty", (0,5), 0.0), ("wide", (10,5), 40.0)];
+        let (packed, leftover) = packer.pack_by_value(items, |&(_, size, _)| size, |&(_, _, value)| value);
+
+        let names: Vec<_> = packed.iter().map(|&((name, _, _), _)| name).collect();
+        assert_eq!(names, vec!["empty", "small", "wide"]);
+        assert_eq!(leftover.len(), 1);
+    }
+
+    #[test]
+    fn most_pack() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (10,10));
+
+        let (packed, leftover) = packer.pack_most(vec![(10,10), (5,5), (4,4), (5,5), (5,10)], |&x| x);
+        assert_eq!(packed.len(), 3);
+        assert_eq!(leftover, vec![(5,10), (10,10)]);
+        assert!(valid_pack(&packed));
+    }
+
+    #[test]
+    fn generated_pack() {
+        for seed in 0..16 {
+            let mut generator = InstanceGenerator::new(seed);
+            let instance = generator.guillotine(Size::new(64,64), 24);
+
+            let mut packer = RectPacker::builder().bin(0, 0, 64, 64).allow_rotation(true).build();
+            let (packed, _) = packer.pack_most(instance.items, |x| x.clone());
+            assert!(valid_pack(&packed));
+            assert!(packed.iter().all(|&(_, ref x)| Rect::new((0,0), (64,64)).supersets(x)));
+        }
+    }
+
+    #[test]
+    fn area_check() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (10,10));
+
+        assert!(packer.fits_by_area(&[(5,10), (5,10)]));
+        assert!(!packer.fits_by_area(&[(5,10), (5,10), (1,1)]));
+        assert!(!packer.fits_by_area(&[(11,1)]));
+
+        // Global packing still packs what fits when the objects fail the check
+        let failed = packer.pack_global(vec![(10,10), (1,1)], |&x| x).err().unwrap();
+        assert_eq!(failed.packed().len(), 1);
+        assert!(packer.free_rects().is_empty());
+    }
+
+    #[test]
+    fn bin_size_estimate() {
+        let sizes = [(10u32,10u32), (10,10), (10,10), (10,10)];
+        assert_eq!(estimate_bin_size(&sizes, 1.0), Size::new(20.0, 20.0));
+        assert_eq!(estimate_bin_size(&sizes, 0.5), Size::new(29.0, 29.0));
+        assert_eq!(estimate_bin_size(&[(40u32,1u32)], 0.5), Size::new(40.0, 2.0));
+        assert_eq!(estimate_bin_size(&[(1u32,40u32)], 0.5), Size::new(2.0, 40.0));
+    }
+
+    #[test]
+    fn queued_pack() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (10,10));
+
+        assert!(packer.pack_or_queue(10,10).is_ok());
+        assert_eq!(packer.pack_or_queue(5,5), Err(0));
+        assert_eq!(packer.pack_or_queue(20,20), Err(1));
+        assert_eq!(packer.queued().len(), 2);
+
+        let mut unqueued = RectPacker::new();
+        unqueued.add_free((0,0), (10,10));
+        unqueued.pack(10,10);
+        assert!(packer != unqueued);
+
+        packer.add_free((10,0), (20,10));
+        assert_eq!(packer.drain_queued(), vec![(0, Rect::new((10,0), (15,5)))]);
+        assert!(packer.drain_queued().is_empty());
+        assert_eq!(packer.queued(), &[(1, Size::new(20,20))]);
+    }
+
+    #[test]
+    fn repeated_sizes() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (20,10));
+
+        let tiles: Vec<_> = (0..10).map(|x| (x, if x % 2 == 0 { (5,5) } else { (5,2) })).collect();
+        let packed = packer.pack_global(tiles, |&(_, size)| size).unwrap();
+        assert!(valid_pack(&packed));
+
+        let order: Vec<_> = packed.iter().map(|&((x, _), _)| x).collect();
+        assert_eq!(order, vec![0, 2, 4, 6, 1, 3, 8, 5, 7, 9]);
+        assert_eq!(packer.stats().heuristic_evaluations, 26);
+    }
+
+    #[test]
+    fn deterministic_ties() {
+        let mut packer = RectPacker::new();
+        packer.add_free((10,0), (20,10));
+        packer.add_free((0,10), (10,20));
+        packer.add_free((0,0), (10,10));
+
+        assert_eq!(packer.pack(10,10).map(|x| x.min), Some(Point::new(0,0)));
+        assert_eq!(packer.pack(10,10).map(|x| x.min), Some(Point::new(10,0)));
+        assert_eq!(packer.pack(10,10).map(|x| x.min), Some(Point::new(0,10)));
+    }
+
+    #[test]
+    fn heuristics() {
+        let heuristics = [Heuristic::Bssf, Heuristic::Blsf, Heuristic::Baf, Heuristic::BottomLeft];
+
+        for &heuristic in heuristics.iter() {
+            let mut packer = RectPacker::builder().bin(0,0,20,20).heuristic(heuristic).build();
+            let packed = packer.pack_global(vec![(10,10), (5,10), (10,5), (5,5), (10,5)], |&x| x).unwrap();
+            assert!(valid_pack(&packed));
+        }
+    }
+
+    #[test]
+    fn rotated_pack() {
+        let mut packer = RectPacker::builder().bin(0,0,10,20).allow_rotation(true).build();
+
+        assert_eq!(packer.pack(20,10).map(|x| x.size()), Some(Size::new(10,20)));
+        assert_eq!(packer.pack(1,1), None);
+    }
+
+    #[test]
+    fn rotated_global_pack() {
+        let mut packer = RectPacker::builder().bin(0,0,10,30).allow_rotation(true).build();
+        let packed = packer.pack_global(vec![(8,5), (20,10)], |&x| x).unwrap();
+
+        assert_eq!(packed.into_vec(), vec![((20,10), Rect::new((0,0), (10,20))), ((8,5), Rect::new((0,20), (8,25)))]);
+    }
+
+    #[test]
+    fn shared_config() {
+        let config = PackerConfig{heuristic: Heuristic::Baf, allow_rotation: true, padding: Some(1), row_pitch: None,
+                                   alignment: None, tie_seed: None, zero_size: ZeroSizePolicy::Pack};
+        let mut packer = RectPacker::with_config(config.clone());
+        packer.add_free((0,0), (10,20));
+        let mut built = RectPacker::builder().bin(0,0,10,20).config(config).build();
+
+        assert_eq!(packer.pack(19,9), built.pack(19,9));
+        assert_eq!(built.config().padding, Some(1));
+    }
+
+    #[test]
+    fn near_pack() {
+        let mut packer = RectPacker::builder().bin(0,0,20,20).build();
+        packer.pack(10,10).unwrap();
+
+        assert_eq!(packer.clone().pack(10,10), Some(Rect::new((10,0), (20,10))));
+        assert_eq!(packer.pack_near(10,10, &Rect::new((0,15), (5,20))), Some(Rect::new((0,10), (10,20))));
+
+        let mut packer = RectPacker::builder().bin(0,0,30,20).build();
+        packer.pack(10,10).unwrap();
+        let frames = packer.pack_frames(&[(10,10), (10,10), (30,10)]);
+        assert_eq!(frames[..2], [Some(Rect::new((0,10), (10,20))), Some(Rect::new((10,0), (20,10)))]);
+        assert_eq!(frames[2], None);
+    }
+
+    #[test]
+    fn padded_pack() {
+        let mut packer = RectPacker::builder().bin(0,0,10,10).padding(1).build();
+
+        assert_eq!(packer.pack(4,4), Some(Rect::new((0,0), (4,4))));
+        assert_eq!(packer.pack(4,4), Some(Rect::new((5,0), (9,4))));
+        assert_eq!(packer.pack(5,5), None);
+    }
+
+    #[test]
+    fn zero_size_pack() {
+        let glyphs = vec![('a', (4,4)), (' ', (0,4)), ('b', (4,4))];
+        let with = |policy| RectPacker::builder().bin(0,0,10,10)
+            .config(PackerConfig{zero_size: policy, padding: Some(1), ..PackerConfig::default()}).build();
+
+        let mut packer = with(ZeroSizePolicy::Pack);
+        assert_eq!(packer.pack(0,4), Some(Rect::new((0,0), (0,4))));
+        assert!(packer.pack(4,4).unwrap().min != Point::new(0,0));
+
+        let mut packer = with(ZeroSizePolicy::Reject);
+        assert_eq!(packer.pack(0,4), None);
+        assert_eq!(packer.pack_global(glyphs.clone(), |&(_, size)| size).unwrap_err().unpacked().len(), 3);
+
+        let mut packer = with(ZeroSizePolicy::Skip);
+        let failed = packer.pack_global(glyphs.clone(), |&(_, size)| size).unwrap_err();
+        assert_eq!((failed.packed().len(), failed.unpacked()), (2, &[(' ', (0,4))][..]));
+
+        let mut packer = with(ZeroSizePolicy::Origin);
+        let packed = packer.pack_global(glyphs, |&(_, size)| size).unwrap();
+        assert_eq!(packed[2], ((' ', (0,4)), Rect::new((0,0), (0,4))));
+        assert_eq!(packer.pack(3,0), Some(Rect::new((0,0), (3,0))));
+        assert_eq!(packer.pack(4,4), Some(Rect::new((0,5), (4,9))));
+    }
+
+    #[test]
+    fn signed_pack() {
+        let mut packer = RectPacker::builder().bin(-512,-512,1024,1024).alignment(4,4).build();
+        assert_eq!(packer.pack(10,10), Some(Rect::new((-512,-512), (-502,-502))));
+
+        let packed = packer.pack_global(0..64, |_| (128i32,100i32)).unwrap();
+        for (i, &(_, ref a)) in packed.iter().enumerate() {
+            assert!(packed[i + 1..].iter().all(|&(_, ref b)| !a.intersects(b)));
+        }
+        assert!(packed.iter().all(|&(_, rect)| rect.min.x % 4 == 0 && rect.min.y % 4 == 0 && rect.max.x <= 512));
+        assert_eq!(packer.bounds(), Some(&Rect::new((-512,-512), (512,512))));
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_size_pack() {
+        let mut packer = RectPacker::builder().bin(-10,-10,20,20).build();
+        packer.pack(-5,5);
+    }
+
+    #[test]
+    fn kerf_pack() {
+        let mut packer = RectPacker::builder().bin(0,0,10,10).kerf(1).build();
+        assert_eq!(packer.bounds(), Some(&Rect::new((0,0), (10,10))));
+
+        assert_eq!(packer.pack(4,10), Some(Rect::new((0,0), (4,10))));
+        assert_eq!(packer.pack(6,10), None);
+        assert_eq!(packer.pack(5,10), Some(Rect::new((5,0), (10,10))));
+        assert_eq!(packer.utilization(), 0.9);
+    }
+
+    #[test]
+    fn aligned_pack() {
+        let mut packer = RectPacker::builder().bin(0,0,16,16).alignment(4,2).build();
+        assert_eq!(packer.pack(3,3), Some(Rect::new((0,0), (3,3))));
+        assert_eq!(packer.pack(5,1), Some(Rect::new((4,0), (9,1))));
+        assert_eq!(packer.pack(16,12), Some(Rect::new((0,4), (16,16))));
+
+        let mut packer = RectPacker::builder().bin(0,0,64,64).config(PackerConfig::mipmapped(2, true)).build();
+        let packed = packer.pack_many(&[(10,10), (1,1), (5,30), (20,3)]);
+        assert!(packed.iter().all(|x| x.map_or(false, |x| x.min.x % 4 == 0 && x.min.y % 4 == 0)));
+        assert_eq!(packer.config().padding, Some(4));
+        assert_eq!(PackerConfig::<u32>::mipmapped(0, false), PackerConfig::default());
+
+        let mut packer = RectPacker::builder().bin(0,0,16,16).row_pitch(8).build();
+        assert_eq!(packer.pack(3,3), Some(Rect::new((0,0), (3,3))));
+        assert_eq!(packer.pack(9,3), Some(Rect::new((0,3), (9,6))));
+        assert_eq!(packer.pack(8,10), Some(Rect::new((0,6), (8,16))));
+
+        let mut packer = RectPacker::with_config(PackerConfig::block_compressed(6,5));
+        packer.add_free((0,0), (30,30));
+        for rect in packer.pack_many(&[(7,7), (3,11), (6,5), (13,1)]).into_iter() {
+            let rect = rect.unwrap();
+            assert!(rect.min.x % 6 == 0 && rect.min.y % 5 == 0);
+        }
+    }
+
+    #[test]
+    fn sized_pack() {
+        let mut packer = RectPacker::builder().bin(0,0,10,10).build();
+        let sizes = [Size::new(10,5), Size::new(10,5)];
+        let packed = packer.pack_global_sized(sizes.iter()).unwrap();
+
+        assert_eq!(packed.iter().map(|x| x.1.size()).collect::<Vec<_>>(), sizes);
+        assert_eq!((3,4).rect_size(), Size::new(3,4));
+        assert_eq!([3,4].rect_size(), Size::new(3,4));
+
+        let mut packer = RectPacker::builder().bin(0,0,10,10).build();
+        let rects = vec![Rect::new((2,2), (12,7)), Rect::new((0,0), (10,5))];
+        let packed = packer.pack_global_sized(rects).unwrap();
+        assert!(packed.iter().all(|&(ref source, ref rect)| source.size() == rect.size()));
+    }
+
+    #[test]
+    fn speculative_pack() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (10,10));
+        packer.pack(5,5).unwrap();
+
+        let mut speculative = packer.clone();
+        assert_eq!(speculative, packer);
+        assert!(speculative.pack_global(vec![(10,5), (5,10)], |&x| x).is_err());
+
+        let failed = packer.pack_global(vec![(10,5), (5,10)], |&x| x).err().unwrap();
+        assert_eq!(failed.clone(), failed);
+        assert_eq!(speculative.free_rects(), packer.free_rects());
+
+        // Only the layout is compared, not how the packer got there
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (10,10));
+        packer.pack(5,5).unwrap();
+        let mut other = RectPacker::new();
+        other.start_recording();
+        other.add_free((0,0), (10,10));
+        assert_eq!(other.pack(20,20), None);
+        other.pack(5,5).unwrap();
+        assert_eq!(other, packer);
+        other.pack(1,1).unwrap();
+        assert!(other != packer);
+    }
+
+    #[test]
+    fn summary() {
+        let mut packer = RectPacker::new();
+        assert_eq!(packer.to_string(), "RectPacker without bounds: 0 free rectangles, 0.0% utilized");
+
+        packer.add_free((0,0), (10,10));
+        packer.pack(5,5).unwrap();
+        assert_eq!(packer.to_string(), "RectPacker (0, 0)..(10, 10): 2 free rectangles, 25.0% utilized");
+        assert!(format!("{:?}", packer).contains("free: [Rect"));
+    }
+
+    #[test]
+    fn cached_placements() {
+        for &allow_rotation in [false, true].iter() {
+            let mut packer = RectPacker::builder().bin(0,0,200,200).allow_rotation(allow_rotation).build();
+            let sizes = [(10,10), (20,5), (5,20), (7,3), (10,10), (3,7)];
+
+            let mut seed = 1u32;
+            for _ in 0..300 {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                let (width, height) = sizes[seed as usize % sizes.len()];
+
+                let rotated = if allow_rotation { packer.fit(&packer.empty, &Size::new(height, width)) } else { None };
+                let expected = super::Candidate::choose(packer.fit(&packer.empty, &Size::new(width, height)), rotated)
+                    .map(|x| (x.position, x.size));
+                let packed = packer.pack(width, height).map(|x| (x.min, x.size()));
+                assert_eq!(packed, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn observed_pack() {
+        #[derive(Default)]
+        struct Counts {
+            placed: usize,
+            split: usize,
+            pruned: usize,
+            removed: usize,
+        }
+
+        struct Counter(Arc<Mutex<Counts>>);
+
+        impl Observer<u32> for Counter {
+            fn on_place(&mut self, _: &Rect<u32>) { self.0.lock().unwrap().placed += 1; }
+            fn on_split(&mut self, _: &Rect<u32>, _: &[Rect<u32>]) { self.0.lock().unwrap().split += 1; }
+            fn on_prune(&mut self, _: &Rect<u32>) { self.0.lock().unwrap().pruned += 1; }
+            fn on_remove(&mut self, _: &Rect<u32>) { self.0.lock().unwrap().removed += 1; }
+        }
+
+        let counts = Arc::new(Mutex::new(Counts::default()));
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (10,10));
+        packer.set_observer(Counter(counts.clone()));
+        packer.pack(5,5).unwrap();
+        packer.pack(5,5).unwrap();
+        packer.set_max_free(Some(0));
+
+        {
+            let counts = counts.lock().unwrap();
+            assert_eq!((counts.placed, counts.split), (2, 2));
+            assert_eq!((counts.pruned, counts.removed), (1, 1));
+        }
+
+        assert!(packer.take_observer().is_some());
+        assert!(packer.clone().take_observer().is_none());
+
+        let counts = Arc::new(Mutex::new(Counts::default()));
+        let mut packer = RectPacker::builder().bin(0,0,10,10).build();
+        packer.set_observer(Counter(counts.clone()));
+        let (packed, _) = packer.pack_most(vec![(5,5), (10,5), (10,10)], |&x| x);
+        assert_eq!(counts.lock().unwrap().placed, packed.len());
+        packer.pack(5,5).unwrap();
+        assert_eq!(counts.lock().unwrap().placed, packed.len() + 1);
+        assert!(packer.take_observer().is_some());
+    }
+
+    #[test]
+    fn replayed_pack() {
+        let mut packer = RectPacker::builder().bin(0,0,50,50).allow_rotation(true).build();
+        packer.pack(10,20).unwrap();
+        packer.start_recording();
+
+        packer.add_free((50,0), (60,50));
+        packer.pack_global(vec![(30,10), (10,30), (5,5)], |&x| x).unwrap();
+        packer.set_max_free(Some(3));
+        packer.pack(20,10).unwrap();
+        packer.pack(100,100);
+        packer.pack_global_interruptible(vec![(1,1), (2,2)], |&x| x, Instant::now()).err().unwrap();
+
+        let recording = packer.stop_recording().unwrap();
+        assert_eq!(recording.len(), 4 + 6);
+
+        let mut replayed = RectPacker::new();
+        replayed.replay(&recording);
+        assert_eq!(replayed.free_rects(), packer.free_rects());
+        assert_eq!(replayed.config(), packer.config());
+
+        let mut larger = RectPacker::builder().bin(0,0,100,100).heuristic(Heuristic::BottomLeft).build();
+        let placements = larger.replay_packing(&recording);
+        let sizes: Vec<Size<u32>> = recording.iter().flat_map(|x| x.sizes()).collect();
+        assert_eq!(placements.len(), 7);
+        assert_eq!(placements[2].map(|x| x.size()), Some(Size::new(5,5)));
+        assert_eq!(placements[4], None);
+        assert_eq!(placements.iter().filter(|x| x.is_some()).count(), 6);
+        assert!(placements.iter().zip(sizes.iter()).all(|(rect, size)| rect.map_or(true, |x| x.size() == *size)));
+    }
+
+    #[test]
+    fn grown_pack() {
+        let sorted = |packer: &RectPacker<u32>| {
+            let mut free = packer.free_rects().to_vec();
+            free.sort_by_key(|x| (x.min.x, x.min.y, x.max.x, x.max.y));
+            free
+        };
+
+        let mut packer = RectPacker::builder().bin(0,0,10,10).build();
+        let mut fresh = RectPacker::builder().bin(0,0,20,15).build();
+        assert_eq!(packer.pack(10,4), fresh.pack(10,4));
+
+        packer.start_recording();
+        packer.grow_to((20,15));
+        assert_eq!(packer.bounds(), Some(&Rect::new((0,0), (20,15))));
+        assert_eq!(sorted(&packer), sorted(&fresh));
+
+        packer.grow_to((5,5));
+        assert_eq!(packer.bounds(), Some(&Rect::new((0,0), (20,15))));
+        packer.grow_to((20,20));
+        assert_eq!(packer.pack(20,16), Some(Rect::new((0,4), (20,20))));
+
+        let recording = packer.stop_recording().unwrap();
+        assert_eq!(&recording[3..], &[Operation::GrowTo(Point::new(20,15)), Operation::GrowTo(Point::new(20,20)),
+                                      Operation::Pack(Size::new(20,16))]);
+        let mut replayed = RectPacker::builder().bin(0,0,10,10).build();
+        replayed.pack(10,4);
+        replayed.replay(&recording);
+        assert_eq!(sorted(&replayed), sorted(&packer));
+
+        // The cut that is not needed at the far edges of a bin moves along with them
+        struct Split(Arc<Mutex<Vec<Rect<u32>>>>);
+        impl Observer<u32> for Split {
+            fn on_split(&mut self, free: &Rect<u32>, _: &[Rect<u32>]) { self.0.lock().unwrap().push(*free); }
+        }
+
+        let split = Arc::new(Mutex::new(Vec::new()));
+        let mut packer = RectPacker::builder().bin(0,0,10,10).kerf(1).build();
+        let mut fresh = RectPacker::builder().bin(0,0,20,10).kerf(1).build();
+        assert_eq!(packer.pack(4,10), fresh.pack(4,10));
+        packer.set_observer(Split(split.clone()));
+        packer.grow_to((20,10));
+        assert_eq!(packer.bounds(), fresh.bounds());
+        assert_eq!(sorted(&packer), sorted(&fresh));
+        assert_eq!(*split.lock().unwrap(), vec![Rect::new((5,0), (11,11))]);
+        assert_eq!(packer.pack(15,10), Some(Rect::new((5,0), (20,10))));
+    }
+
+    #[test]
+    fn seeded_ties() {
+        let layout = |seed| {
+            let mut packer = RectPacker::builder().bin(0,0,20,20).tie_seed(seed).build();
+            packer.pack_global(vec![(10,10), (10,10), (10,10)], |&x| x).unwrap()
+        };
+
+        assert_eq!(layout(1), layout(1));
+        assert!((0..16).any(|seed| layout(seed) != layout(0)));
+
+        let mut packer = RectPacker::builder().bin(0,0,20,20).tie_seed(3).build();
+        let packed: Vec<_> = (0..4).map(|_| packer.pack(10,10).unwrap()).collect();
+        assert_eq!(packed.iter().filter(|x| x.min.x % 10 == 0 && x.min.y % 10 == 0).count(), 4);
+    }
+
+    #[test]
+    fn portfolio() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (10,20));
+
+        let upright = PackerConfig::default();
+        let rotated = PackerConfig{allow_rotation: true, ..PackerConfig::default()};
+        let (index, packed) = packer.solve_portfolio(&[upright.clone(), rotated], vec![(20,10)], |&x| x).unwrap();
+        assert_eq!(index, 1);
+        assert_eq!(packed[0].1, Rect::new((0,0), (10,20)));
+        assert!(packer.free_rects().is_empty());
+
+        struct Placed(Arc<Mutex<usize>>);
+        impl Observer<u32> for Placed {
+            fn on_place(&mut self, _: &Rect<u32>) { *self.0.lock().unwrap() += 1; }
+        }
+
+        let placed = Arc::new(Mutex::new(0));
+        let mut packer = RectPacker::builder().bin(0,0,10,10).build();
+        packer.set_observer(Placed(placed.clone()));
+        packer.solve_portfolio(&[upright.clone()], vec![(5,5)], |&x| x).unwrap();
+        packer.pack(5,5).unwrap();
+        assert_eq!(*placed.lock().unwrap(), 1);
+        assert!(packer.take_observer().is_some());
+
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (10,10));
+        let failed = packer.solve_portfolio(&[upright], vec![(6,6), (6,6)], |&x| x).err().unwrap();
+        assert_eq!((failed.packed().len(), failed.unpacked()), (1, &[(6,6)][..]));
+    }
+
+    #[test]
+    fn tagged_pack() {
+        let packer = RectPacker::builder().bin(0,0,10,10).build();
+        let objects = vec![("ui", (10,5)), ("terrain", (10,10)), ("ui", (10,5))];
+        let pages = packer.pack_global_by_tag(objects, |&(tag, size)| (tag, size));
+
+        assert_eq!(pages.len(), 2);
+        let (ref ui, ref packed) = pages["ui"];
+        assert_eq!(packed.as_ref().map(|x| x.len()), Ok(2));
+        assert!(ui.free_rects().is_empty());
+        let (ref terrain, ref packed) = pages["terrain"];
+        assert_eq!(packed.as_ref().map(|x| x[0].1), Ok(Rect::new((0,0), (10,10))));
+        assert!(terrain.free_rects().is_empty());
+        assert_eq!(packer.free_rects().len(), 1);
+    }
+
+    #[test]
+    fn free_list_is_maximal() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (100,100));
+        packer.add_free((10,10), (20,20));
+        assert_eq!(packer.empty.len(), 1);
+
+        for &(width, height) in [(30,10), (10,30), (25,25), (5,40), (40,5)].iter() {
+            packer.pack(width, height).unwrap();
+
+            for (i, a) in packer.empty.iter().enumerate() {
+                for (j, b) in packer.empty.iter().enumerate() {
+                    assert!(i == j || !a.supersets(b));
+                }
+            }
+
+            assert!(packer.empty.windows(2).all(|pair| pair[0].min.x <= pair[1].min.x));
+        }
+    }
+
+    #[test]
+    fn bounded_free_list() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (100,100));
+        packer.set_max_free(Some(2));
+
+        for _ in 0..10 {
+            packer.pack(7,13).unwrap();
+            assert!(packer.empty.len() <= 2);
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (100,100));
+        packer.pack(10,10).unwrap();
+        packer.pack(200,10);
+
+        let stats = packer.stats();
+        assert_eq!(stats.packs_attempted, 2);
+        assert_eq!(stats.heuristic_evaluations, 3);
+        assert_eq!(stats.peak_free, 2);
+        assert_eq!((stats.prune_passes, stats.pruned_rects), (3, 0));
+    }
+
+    #[test]
+    fn global_pack_progress() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (10,10));
+
+        let mut reports = Vec::new();
+        packer.pack_global_with_progress(vec![(5,10), (5,5)], |&x| x, |progress| {
+            reports.push((progress.placed, progress.remaining, progress.utilization()));
+        }).unwrap();
+
+        assert_eq!(reports, vec![(1, 1, 0.5), (2, 0, 0.75)]);
+    }
+
+    #[test]
+    fn interrupted_global_pack() {
+        let mut packer = RectPacker::new();
+        packer.add_free((0,0), (100,100));
+
+        let cancel = AtomicBool::new(true);
+        let failed = packer.pack_global_interruptible(vec![(10,10), (20,20)], |&x| x, &cancel)
+            .err().unwrap();
+        assert!(failed.interrupted());
+        assert_eq!(failed.packed().len(), 0);
+        assert_eq!(failed.unpacked().len(), 2);
     }
 }
\ No newline at end of file
@@ -0,0 +1,73 @@
+use std::fmt;
+
+use {MaybeSync, Rect};
+
+/// Receives the events of a `RectPacker` as they happen, e.g. to drive a live visualization or
+/// collect statistics. Every callback does nothing by default, so only the events of interest
+/// need to be implemented.
+///
+/// See `RectPacker::set_observer`.
+pub trait Observer<S>: Send + MaybeSync {
+    /// A rectangle has been packed
+    fn on_place(&mut self, _rect: &Rect<S>) {}
+
+    /// A free rectangle intersecting a packed rectangle has been replaced by the parts of it not
+    /// covered by the packed rectangle
+    fn on_split(&mut self, _free: &Rect<S>, _parts: &[Rect<S>]) {}
+
+    /// A free rectangle has been discarded for being contained in another free rectangle
+    fn on_prune(&mut self, _free: &Rect<S>) {}
+
+    /// A free rectangle has been dropped for exceeding the limit set by `RectPacker::set_max_free`
+    fn on_remove(&mut self, _free: &Rect<S>) {}
+}
+
+/// Holds the observer of a packer, if any. Clones of a packer do not share its observer, and
+/// observers are ignored when comparing packers.
+pub struct ObserverSlot<S>(Option<Box<dyn Observer<S>>>);
+
+impl<S> ObserverSlot<S> {
+    pub fn new() -> ObserverSlot<S> {
+        ObserverSlot(None)
+    }
+
+    pub fn set(&mut self, observer: Option<Box<dyn Observer<S>>>) -> Option<Box<dyn Observer<S>>> {
+        ::std::mem::replace(&mut self.0, observer)
+    }
+
+    pub fn place(&mut self, rect: &Rect<S>) {
+        if let Some(ref mut observer) = self.0 {
+            observer.on_place(rect);
+        }
+    }
+
+    pub fn split(&mut self, free: &Rect<S>, parts: &[Rect<S>]) {
+        if let Some(ref mut observer) = self.0 {
+            observer.on_split(free, parts);
+        }
+    }
+
+    pub fn prune(&mut self, free: &Rect<S>) {
+        if let Some(ref mut observer) = self.0 {
+            observer.on_prune(free);
+        }
+    }
+
+    pub fn remove(&mut self, free: &Rect<S>) {
+        if let Some(ref mut observer) = self.0 {
+            observer.on_remove(free);
+        }
+    }
+}
+
+impl<S> Clone for ObserverSlot<S> {
+    fn clone(&self) -> ObserverSlot<S> {
+        ObserverSlot(None)
+    }
+}
+
+impl<S> fmt::Debug for ObserverSlot<S> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        formatter.write_str(if self.0.is_some() { "Some(..)" } else { "None" })
+    }
+}
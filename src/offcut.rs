@@ -0,0 +1,78 @@
+use std::cmp::Ordering;
+use std::ops::{Add, Sub, Mul};
+
+use {RectPacker, RectPackerBuilder, MaybeDebug, MinMaxIteratorExt};
+use rect::Rect;
+use size::Size;
+use waste::difference;
+
+impl<S> RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    /// The free space that can hold a rectangle of size `min_size` as disjoint rectangles within
+    /// the bounds, e.g. to keep the leftover stock of a cutting job as offcuts for the next one,
+    /// see `RectPackerBuilder::offcuts`.
+    ///
+    /// The free rectangles overlap, so they are taken greedily, largest first, each without the
+    /// space taken so far, for as long as what remains of one holds `min_size` as in `waste`.
+    pub fn offcuts(&self, min_size: &Size<S>) -> Vec<Rect<S>> {
+        let bounds = match self.bounds {
+            Some(ref bounds) => bounds,
+            None => return Vec::new(),
+        };
+
+        let mut sizes = vec![self.padded(min_size)];
+        if self.config.allow_rotation {
+            sizes.push(self.padded(&Size::new(min_size.height.clone(), min_size.width.clone())));
+        }
+        let holds = |free: &Rect<S>| sizes.iter().any(|x| x.width <= free.width() && x.height <= free.height());
+
+        let mut pieces: Vec<Rect<S>> = self.empty.iter().filter(|x| holds(x)).cloned().collect();
+        let mut offcuts = Vec::new();
+        loop {
+            let largest = (0..pieces.len())
+                .filter(|&x| holds(&pieces[x]))
+                .max_cmp(|&a, &b| pieces[a].area().partial_cmp(&pieces[b].area()).unwrap_or(Ordering::Equal));
+            let offcut = match largest {
+                Some(index) => pieces.remove(index),
+                None => break,
+            };
+            pieces = pieces.iter().flat_map(|x| difference(x, &offcut)).collect();
+            offcuts.extend(offcut.intersection(bounds));
+        }
+
+        offcuts
+    }
+}
+
+impl<S> RectPackerBuilder<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    /// Adds offcuts left over from an earlier job, as returned by `RectPacker::offcuts`, as bins
+    pub fn offcuts<I>(self, offcuts: I) -> RectPackerBuilder<S> where I: IntoIterator<Item=Rect<S>> {
+        offcuts.into_iter().fold(self, |builder, x| {
+            let size = x.size();
+            builder.bin(x.min.x, x.min.y, size.width, size.height)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use RectPacker;
+    use rect::Rect;
+    use size::Size;
+
+    #[test]
+    fn offcut_reuse() {
+        let mut packer = RectPacker::builder().bin(0,0,10,10).build();
+        packer.pack(6,6);
+        assert_eq!(packer.offcuts(&Size::new(2,2)), vec![Rect::new((0,6), (10,10)), Rect::new((6,0), (10,6))]);
+        assert_eq!(packer.offcuts(&Size::new(5,5)), vec![]);
+
+        let mut packer = RectPacker::builder().bin(0,0,10,10).kerf(1).build();
+        packer.pack(6,10);
+        let offcuts = packer.offcuts(&Size::new(2,2));
+        assert_eq!(offcuts, vec![Rect::new((7,0), (10,10))]);
+
+        let mut next = RectPacker::builder().kerf(1).offcuts(offcuts).build();
+        assert_eq!(next.bounds(), Some(&Rect::new((7,0), (10,10))));
+        assert_eq!(next.pack(3,10), Some(Rect::new((7,0), (10,10))));
+    }
+}
@@ -0,0 +1,116 @@
+use std::cmp::Ordering;
+use std::ops::{Deref, Add, Sub, Mul};
+use std::slice;
+use std::vec;
+
+use sum;
+use rect::Rect;
+
+/// The objects packed by `RectPacker::pack_global`, each with its rectangle, in an arbitrary
+/// order. It derefs to a slice of the objects and their rectangles, and converts back into a
+/// `Vec` of them.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PackedResult<T,S> {
+    packed: Vec<(T, Rect<S>)>,
+    bounds: Option<Rect<S>>,
+}
+
+impl<T,S> PackedResult<T,S> {
+    /// Creates a result from the packed objects and the bounds of the packer they were packed
+    /// into, which `utilization` is relative to
+    pub fn new(packed: Vec<(T, Rect<S>)>, bounds: Option<Rect<S>>) -> PackedResult<T,S> {
+        PackedResult{packed: packed, bounds: bounds}
+    }
+
+    /// The rectangle `object` was packed into, or `None` if it is not among the packed objects.
+    /// If it is packed more than once, the first of its rectangles is returned.
+    pub fn get(&self, object: &T) -> Option<&Rect<S>> where T: PartialEq {
+        self.packed.iter().find(|x| x.0 == *object).map(|x| &x.1)
+    }
+
+    /// Iterates over the packed objects and their rectangles
+    pub fn iter<'a>(&'a self) -> slice::Iter<'a, (T, Rect<S>)> {
+        self.packed.iter()
+    }
+
+    /// The bounds of the packer the objects were packed into
+    pub fn bounds(&self) -> Option<&Rect<S>> {
+        self.bounds.as_ref()
+    }
+
+    /// Sorts the packed objects by position, top to bottom and then left to right, e.g. to
+    /// write them in a stable order regardless of the order they were packed in
+    pub fn sort_by_position(&mut self) where S: PartialOrd {
+        self.packed.sort_by(|a, b| {
+            let (a, b) = (&a.1.min, &b.1.min);
+            a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal)
+                .then(a.x.partial_cmp(&b.x).unwrap_or(Ordering::Equal))
+        });
+    }
+
+    /// The fraction of the bounds covered by the packed rectangles, or 0 if there are no bounds
+    pub fn utilization(&self) -> f64 where S: Clone + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + Into<f64> {
+        match (sum(self.packed.iter().map(|x| x.1.area())), &self.bounds) {
+            (Some(area), &Some(ref bounds)) => area.into() / bounds.area().into(),
+            _ => 0.0,
+        }
+    }
+
+    /// The packed objects and their rectangles
+    pub fn into_vec(self) -> Vec<(T, Rect<S>)> {
+        self.packed
+    }
+}
+
+impl<T,S> Deref for PackedResult<T,S> {
+    type Target = [(T, Rect<S>)];
+
+    fn deref(&self) -> &[(T, Rect<S>)] {
+        &self.packed
+    }
+}
+
+impl<T,S> From<PackedResult<T,S>> for Vec<(T, Rect<S>)> {
+    fn from(result: PackedResult<T,S>) -> Vec<(T, Rect<S>)> {
+        result.packed
+    }
+}
+
+impl<T,S> IntoIterator for PackedResult<T,S> {
+    type Item = (T, Rect<S>);
+    type IntoIter = vec::IntoIter<(T, Rect<S>)>;
+
+    fn into_iter(self) -> vec::IntoIter<(T, Rect<S>)> {
+        self.packed.into_iter()
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a PackedResult<T,S> {
+    type Item = &'a (T, Rect<S>);
+    type IntoIter = slice::Iter<'a, (T, Rect<S>)>;
+
+    fn into_iter(self) -> slice::Iter<'a, (T, Rect<S>)> {
+        self.packed.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use RectPacker;
+    use rect::Rect;
+
+    #[test]
+    fn packed_result() {
+        let mut packer = RectPacker::builder().bin(0,0,10,10).build();
+        let mut packed = packer.pack_global(vec!["a", "b", "c"], |&x| if x == "a" { (10,5) } else { (5,5) }).unwrap();
+
+        assert_eq!(packed.get(&"a"), Some(&Rect::new((0,0), (10,5))));
+        assert_eq!(packed.get(&"d"), None);
+        assert_eq!(packed.utilization(), 1.0);
+
+        packed.sort_by_position();
+        let positions: Vec<_> = packed.iter().map(|x| (x.1.min.x, x.1.min.y)).collect();
+        assert_eq!(positions, vec![(0,0), (0,5), (5,5)]);
+        assert_eq!(Vec::from(packed).len(), 3);
+    }
+}
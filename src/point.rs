@@ -0,0 +1,87 @@
+/// A position given by its x and y coordinates
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Point<S> {
+    pub x: S,
+    pub y: S,
+}
+
+impl<S> Point<S> {
+    /// Creates a point from its coordinates
+    pub fn new(x: S, y: S) -> Point<S> {
+        Point{x: x, y: y}
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl<S> ::quickcheck::Arbitrary for Point<S> where S: ::quickcheck::Arbitrary {
+    fn arbitrary(g: &mut ::quickcheck::Gen) -> Point<S> {
+        Point::new(S::arbitrary(g), S::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item=Point<S>>> {
+        Box::new((self.x.clone(), self.y.clone()).shrink().map(|(x, y)| Point::new(x, y)))
+    }
+}
+
+impl<S> From<(S,S)> for Point<S> {
+    fn from((x, y): (S,S)) -> Point<S> {
+        Point::new(x, y)
+    }
+}
+
+impl<S> From<Point<S>> for (S,S) {
+    fn from(point: Point<S>) -> (S,S) {
+        (point.x, point.y)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<S> From<::mint::Point2<S>> for Point<S> {
+    fn from(point: ::mint::Point2<S>) -> Point<S> {
+        Point::new(point.x, point.y)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<S> From<Point<S>> for ::mint::Point2<S> {
+    fn from(point: Point<S>) -> ::mint::Point2<S> {
+        ::mint::Point2{x: point.x, y: point.y}
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<S, U> From<::euclid::Point2D<S, U>> for Point<S> {
+    fn from(point: ::euclid::Point2D<S, U>) -> Point<S> {
+        Point::new(point.x, point.y)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<S, U> From<Point<S>> for ::euclid::Point2D<S, U> {
+    fn from(point: Point<S>) -> ::euclid::Point2D<S, U> {
+        ::euclid::Point2D::new(point.x, point.y)
+    }
+}
+
+// glam vectors are converted for the scalar type matching their components
+#[cfg(feature = "glam")]
+macro_rules! glam_conversions {
+    ($($vector:ident: $scalar:ty),*) => ($(
+        impl From<::glam::$vector> for Point<$scalar> {
+            fn from(point: ::glam::$vector) -> Point<$scalar> {
+                Point::new(point.x, point.y)
+            }
+        }
+
+        impl From<Point<$scalar>> for ::glam::$vector {
+            fn from(point: Point<$scalar>) -> ::glam::$vector {
+                ::glam::$vector::new(point.x, point.y)
+            }
+        }
+    )*)
+}
+
+#[cfg(feature = "glam")]
+glam_conversions!(Vec2: f32, IVec2: i32, UVec2: u32);
@@ -0,0 +1,266 @@
+use std::ops::{Add, Sub, Mul};
+
+use point::Point;
+use size::Size;
+
+/// An axis-aligned rectangle defined by a minimum and a maximum coordinate. The rectangle is
+/// half-open: `min` is considered to be inside the rectangle while `max` is not.
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Rect<S> {
+    pub min: Point<S>,
+    pub max: Point<S>,
+}
+
+impl<S> Rect<S> {
+    /// Creates a rectangle from its minimum and maximum coordinates
+    pub fn new<A, B>(min: A, max: B) -> Rect<S> where A: Into<Point<S>>, B: Into<Point<S>> {
+        Rect{min: min.into(), max: max.into()}
+    }
+}
+
+impl<S> Rect<S> where S: Clone + Add<S, Output=S> {
+    /// Creates a rectangle from the position of its minimum coordinate and its size
+    pub fn with_size<P, Z>(position: P, size: Z) -> Rect<S> where P: Into<Point<S>>, Z: Into<Size<S>> {
+        let (position, size) = (position.into(), size.into());
+        let max = Point::new(position.x.clone() + size.width, position.y.clone() + size.height);
+        Rect::new(position, max)
+    }
+}
+
+impl<S> Rect<S> where S: PartialOrd {
+    /// Whether a rectangle intersects another rectangle
+    pub fn intersects(&self, other: &Rect<S>) -> bool {
+        self.min.x < other.max.x &&
+        self.min.y < other.max.y &&
+        self.max.x > other.min.x &&
+        self.max.y > other.min.y
+    }
+
+    /// Determines if a rectangle is a superset of (contains all of) another rectangle
+    pub fn supersets(&self, other: &Rect<S>) -> bool {
+        self.min.x <= other.min.x &&
+        self.min.y <= other.min.y &&
+        self.max.x >= other.max.x &&
+        self.max.y >= other.max.y
+    }
+
+    /// Whether a point lies within the rectangle
+    pub fn contains(&self, point: &Point<S>) -> bool {
+        self.min.x <= point.x &&
+        self.min.y <= point.y &&
+        self.max.x > point.x &&
+        self.max.y > point.y
+    }
+}
+
+impl<S> Rect<S> where S: Clone + PartialOrd {
+    /// The smallest rectangle containing both this rectangle and another rectangle
+    pub fn union(&self, other: &Rect<S>) -> Rect<S> {
+        Rect::new(Point::new(lesser(&self.min.x, &other.min.x), lesser(&self.min.y, &other.min.y)),
+                  Point::new(greater(&self.max.x, &other.max.x), greater(&self.max.y, &other.max.y)))
+    }
+
+    /// The rectangle covered by both this rectangle and another rectangle, or `None` if they
+    /// do not intersect
+    pub fn intersection(&self, other: &Rect<S>) -> Option<Rect<S>> {
+        if self.intersects(other) {
+            Some(Rect::new(Point::new(greater(&self.min.x, &other.min.x), greater(&self.min.y, &other.min.y)),
+                           Point::new(lesser(&self.max.x, &other.max.x), lesser(&self.max.y, &other.max.y))))
+        } else {
+            None
+        }
+    }
+}
+
+impl<S> Rect<S> where S: Clone + Sub<S, Output=S> {
+    /// The width of the rectangle
+    pub fn width(&self) -> S {
+        self.max.x.clone() - self.min.x.clone()
+    }
+
+    /// The height of the rectangle
+    pub fn height(&self) -> S {
+        self.max.y.clone() - self.min.y.clone()
+    }
+
+    /// The width and height of the rectangle
+    pub fn size(&self) -> Size<S> {
+        Size::new(self.width(), self.height())
+    }
+}
+
+impl<S> Rect<S> where S: Clone + Sub<S, Output=S> + Mul<S, Output=S> {
+    /// The area of the rectangle
+    pub fn area(&self) -> S {
+        self.width() * self.height()
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<S, U> From<::euclid::Box2D<S, U>> for Rect<S> {
+    fn from(rect: ::euclid::Box2D<S, U>) -> Rect<S> {
+        Rect::new(rect.min, rect.max)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<S, U> From<Rect<S>> for ::euclid::Box2D<S, U> {
+    fn from(rect: Rect<S>) -> ::euclid::Box2D<S, U> {
+        ::euclid::Box2D::new(rect.min.into(), rect.max.into())
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<S, U> From<::euclid::Rect<S, U>> for Rect<S> where S: Clone + Add<S, Output=S> {
+    fn from(rect: ::euclid::Rect<S, U>) -> Rect<S> {
+        Rect::with_size(rect.origin, rect.size)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<S, U> From<Rect<S>> for ::euclid::Rect<S, U> where S: Clone + Sub<S, Output=S> {
+    fn from(rect: Rect<S>) -> ::euclid::Rect<S, U> {
+        let size = rect.size();
+        ::euclid::Rect::new(rect.min.into(), size.into())
+    }
+}
+
+/// Generates rectangles with `min` no more than `max`, so that every generated rectangle is valid.
+#[cfg(feature = "arbitrary")]
+impl<'a, S> ::arbitrary::Arbitrary<'a> for Rect<S> where S: ::arbitrary::Arbitrary<'a> + Clone + PartialOrd {
+    fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Rect<S>> {
+        let (a, b) = ::arbitrary::Arbitrary::arbitrary(u)?;
+        Ok(Rect::ordered(a, b))
+    }
+}
+
+/// Generates rectangles with `min` no more than `max`, and shrinks them to such rectangles.
+#[cfg(feature = "quickcheck")]
+impl<S> ::quickcheck::Arbitrary for Rect<S> where S: ::quickcheck::Arbitrary + PartialOrd {
+    fn arbitrary(g: &mut ::quickcheck::Gen) -> Rect<S> {
+        Rect::ordered(Point::arbitrary(g), Point::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item=Rect<S>>> {
+        Box::new((self.min.clone(), self.max.clone()).shrink().map(|(a, b)| Rect::ordered(a, b)))
+    }
+}
+
+#[cfg(any(feature = "arbitrary", feature = "quickcheck"))]
+impl<S> Rect<S> where S: Clone + PartialOrd {
+    /// The rectangle with two points as opposite corners
+    fn ordered(a: Point<S>, b: Point<S>) -> Rect<S> {
+        Rect::new(Point::new(lesser(&a.x, &b.x), lesser(&a.y, &b.y)),
+                  Point::new(greater(&a.x, &b.x), greater(&a.y, &b.y)))
+    }
+}
+
+fn lesser<S>(a: &S, b: &S) -> S where S: Clone + PartialOrd {
+    if *b < *a { b.clone() } else { a.clone() }
+}
+
+fn greater<S>(a: &S, b: &S) -> S where S: Clone + PartialOrd {
+    if *b > *a { b.clone() } else { a.clone() }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rect;
+    use point::Point;
+
+    #[test]
+    fn geometry() {
+        let a = Rect::with_size((0,0), (10,20));
+        let b = Rect::new((5,15), (15,25));
+
+        assert_eq!((a.width(), a.height(), a.area()), (10, 20, 200));
+        assert!(a.contains(&Point::new(0,0)) && !a.contains(&Point::new(10,0)));
+        assert_eq!(a.intersection(&b), Some(Rect::new((5,15), (10,20))));
+        assert_eq!(a.union(&b), Rect::new((0,0), (15,25)));
+        assert_eq!(a.intersection(&Rect::new((10,0), (20,20))), None);
+    }
+
+    #[cfg(feature = "mint")]
+    #[test]
+    fn mint_conversions() {
+        use size::{Size, SizeSource};
+
+        let point: ::mint::Point2<i32> = Point::new(3,4).into();
+        assert_eq!((point.x, point.y), (3, 4));
+        assert_eq!(Point::from(point), Point::new(3,4));
+
+        let size: ::mint::Vector2<u32> = Size::new(5,6).into();
+        assert_eq!((size.x, size.y), (5, 6));
+        assert_eq!(Size::from(size), Size::new(5,6));
+        assert_eq!(size.rect_size(), Size::new(5,6));
+    }
+
+    #[cfg(feature = "euclid")]
+    #[test]
+    fn euclid_conversions() {
+        use euclid::{Point2D, Size2D, Box2D, UnknownUnit};
+        use size::{Size, SizeSource};
+
+        let point: Point2D<i32, UnknownUnit> = Point::new(3,4).into();
+        assert_eq!(point, Point2D::new(3,4));
+        assert_eq!(Point::from(point), Point::new(3,4));
+
+        let size: Size2D<u32, UnknownUnit> = Size::new(5,6).into();
+        assert_eq!(size, Size2D::new(5,6));
+        assert_eq!(Size::from(size), Size::new(5,6));
+        assert_eq!(size.rect_size(), Size::new(5,6));
+
+        let rect = Rect::new((1,2), (6,8));
+        let boxed: Box2D<i32, UnknownUnit> = rect.clone().into();
+        assert_eq!(boxed, Box2D::new(Point2D::new(1,2), Point2D::new(6,8)));
+        assert_eq!(Rect::from(boxed), rect);
+
+        let sized: ::euclid::Rect<i32, UnknownUnit> = rect.clone().into();
+        assert_eq!(sized, ::euclid::Rect::new(Point2D::new(1,2), Size2D::new(5,6)));
+        assert_eq!(Rect::from(sized), rect);
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn glam_conversions() {
+        use glam::{Vec2, IVec2, UVec2};
+        use size::{Size, SizeSource};
+
+        let point: Vec2 = Point::new(1.5, 2.5).into();
+        assert_eq!(point, Vec2::new(1.5, 2.5));
+        assert_eq!(Point::from(point), Point::new(1.5, 2.5));
+        let point: IVec2 = Point::new(-3,4).into();
+        assert_eq!(Point::from(point), Point::new(-3,4));
+
+        let size: UVec2 = Size::new(5,6).into();
+        assert_eq!(size, UVec2::new(5,6));
+        assert_eq!(Size::from(size), Size::new(5,6));
+        assert_eq!(size.rect_size(), Size::new(5,6));
+        assert_eq!(Size::from(Vec2::from(Size::new(0.5, 1.0))), Size::new(0.5, 1.0));
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_rects() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let bytes: Vec<u8> = (0..64u8).map(|x| x.wrapping_mul(37)).collect();
+        let mut u = Unstructured::new(&bytes);
+        for _ in 0..4 {
+            let rect = Rect::<i32>::arbitrary(&mut u).unwrap();
+            assert!(rect.min.x <= rect.max.x && rect.min.y <= rect.max.y);
+        }
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn quickcheck_rects() {
+        use quickcheck::{quickcheck, Arbitrary};
+
+        fn ordered(rect: Rect<i32>) -> bool {
+            rect.shrink().chain(Some(rect)).all(|x| x.min.x <= x.max.x && x.min.y <= x.max.y)
+        }
+        quickcheck(ordered as fn(Rect<i32>) -> bool);
+    }
+}
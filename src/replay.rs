@@ -0,0 +1,102 @@
+use config::PackerConfig;
+use grain::Grain;
+use rect::Rect;
+use size::Size;
+use point::Point;
+
+/// A public operation on a `RectPacker`, as recorded by `RectPacker::start_recording` and
+/// re-executed by `RectPacker::replay`.
+///
+/// With the `serde` feature enabled, operations can be serialized, e.g. to attach a recording to
+/// a bug report.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum Operation<S> {
+    /// A free rectangle added by `add_free`
+    AddFree(Rect<S>),
+    /// The maximum the bounds were grown to by `grow_to`
+    GrowTo(Point<S>),
+    /// A rectangle packed by `pack`
+    Pack(Size<S>),
+    /// A rectangle packed by `pack_near`, and the rectangle it was packed near
+    PackNear(Size<S>, Rect<S>),
+    /// The sizes of the objects globally packed by `pack_global`, and the number of placements
+    /// made before it stopped
+    PackGlobal(Vec<Size<S>>, usize),
+    /// The sizes and grain directions of the objects globally packed by `pack_global_with_grain`,
+    /// and the number of placements made before it stopped
+    PackGlobalWithGrain(Vec<(Size<S>, Grain)>, usize),
+    /// A limit set by `set_max_free`
+    SetMaxFree(Option<usize>),
+    /// Options set by `set_config`
+    SetConfig(PackerConfig<S>),
+}
+
+impl<S> Operation<S> where S: Clone {
+    /// The sizes of the rectangles the operation packs, in the order they were given, or none if
+    /// it does not pack anything
+    pub fn sizes(&self) -> Vec<Size<S>> {
+        match *self {
+            Operation::Pack(ref size) | Operation::PackNear(ref size, _) => vec![size.clone()],
+            Operation::PackGlobal(ref sizes, _) => sizes.clone(),
+            Operation::PackGlobalWithGrain(ref items, _) => items.iter().map(|x| x.0.clone()).collect(),
+            Operation::AddFree(_) | Operation::GrowTo(_) | Operation::SetMaxFree(_) | Operation::SetConfig(_) => Vec::new(),
+        }
+    }
+}
+
+/// Generates valid operations, so that a generated sequence of operations can be replayed to test
+/// a packer. Operations are shrunk by shrinking their arguments.
+#[cfg(feature = "quickcheck")]
+impl<S> ::quickcheck::Arbitrary for Operation<S> where S: ::quickcheck::Arbitrary + PartialOrd + Default {
+    fn arbitrary(g: &mut ::quickcheck::Gen) -> Operation<S> {
+        use quickcheck::Arbitrary;
+
+        match *g.choose(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap() {
+            0 => Operation::AddFree(Arbitrary::arbitrary(g)),
+            1 => Operation::Pack(Arbitrary::arbitrary(g)),
+            2 => Operation::PackNear(Arbitrary::arbitrary(g), Arbitrary::arbitrary(g)),
+            3 => Operation::PackGlobal(Arbitrary::arbitrary(g), Arbitrary::arbitrary(g)),
+            4 => Operation::SetMaxFree(Arbitrary::arbitrary(g)),
+            5 => Operation::PackGlobalWithGrain(Arbitrary::arbitrary(g), Arbitrary::arbitrary(g)),
+            6 => Operation::GrowTo(Arbitrary::arbitrary(g)),
+            _ => Operation::SetConfig(Arbitrary::arbitrary(g)),
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item=Operation<S>>> {
+        match *self {
+            Operation::AddFree(ref rect) => Box::new(rect.shrink().map(Operation::AddFree)),
+            Operation::GrowTo(ref max) => Box::new(max.shrink().map(Operation::GrowTo)),
+            Operation::Pack(ref size) => Box::new(size.shrink().map(Operation::Pack)),
+            Operation::PackNear(ref size, ref near) => Box::new((size.clone(), near.clone()).shrink()
+                .map(|(size, near)| Operation::PackNear(size, near))),
+            Operation::PackGlobal(ref sizes, placements) => Box::new((sizes.clone(), placements).shrink()
+                .map(|(sizes, placements)| Operation::PackGlobal(sizes, placements))),
+            Operation::PackGlobalWithGrain(ref items, placements) => Box::new((items.clone(), placements).shrink()
+                .map(|(items, placements)| Operation::PackGlobalWithGrain(items, placements))),
+            Operation::SetMaxFree(limit) => Box::new(limit.shrink().map(Operation::SetMaxFree)),
+            Operation::SetConfig(_) => ::quickcheck::empty_shrinker(),
+        }
+    }
+}
+
+/// Generates valid operations, so that a generated sequence of operations can be replayed to fuzz
+/// a packer.
+#[cfg(feature = "arbitrary")]
+impl<'a, S> ::arbitrary::Arbitrary<'a> for Operation<S> where S: ::arbitrary::Arbitrary<'a> + Clone + PartialOrd + Default {
+    fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Operation<S>> {
+        use arbitrary::Arbitrary;
+
+        Ok(match u.int_in_range(0..=7)? {
+            0 => Operation::AddFree(Arbitrary::arbitrary(u)?),
+            1 => Operation::Pack(Arbitrary::arbitrary(u)?),
+            2 => Operation::PackNear(Arbitrary::arbitrary(u)?, Arbitrary::arbitrary(u)?),
+            3 => Operation::PackGlobal(Arbitrary::arbitrary(u)?, Arbitrary::arbitrary(u)?),
+            4 => Operation::SetMaxFree(Arbitrary::arbitrary(u)?),
+            5 => Operation::PackGlobalWithGrain(Arbitrary::arbitrary(u)?, Arbitrary::arbitrary(u)?),
+            6 => Operation::GrowTo(Arbitrary::arbitrary(u)?),
+            _ => Operation::SetConfig(Arbitrary::arbitrary(u)?),
+        })
+    }
+}
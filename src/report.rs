@@ -0,0 +1,88 @@
+use std::ops::{Add, Sub, Mul};
+use std::time::Duration;
+
+use {RectPacker, FailedPacking, PackedResult, Stats, MaybeDebug};
+use rect::Rect;
+use size::Size;
+
+/// A summary of a packing, as returned by `RectPacker::report`, e.g. for asset pipelines to
+/// archive and compare between builds. With the `serde` feature enabled, it can be serialized,
+/// e.g. to JSON with `serde_json`.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct PackingReport<K, S> {
+    /// Every bin
+    pub bins: Vec<BinReport<S>>,
+    /// The key and rectangle of every packed object, and the index of its bin
+    pub placements: Vec<(K, usize, Rect<S>)>,
+    /// The keys of the objects that were not packed
+    pub unpacked: Vec<K>,
+    /// The time spent packing, as measured by the caller
+    pub elapsed: Duration,
+    /// The counters of the packer
+    pub stats: Stats,
+}
+
+/// A summary of a bin in a `PackingReport`.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct BinReport<S> {
+    /// The bounds of the bin
+    pub bounds: Rect<S>,
+    /// The fraction of the bin covered by packed rectangles
+    pub utilization: f64,
+    /// The free space too small for the given size, see `RectPacker::waste`
+    pub waste: Vec<Rect<S>>,
+}
+
+impl<S> RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug + Into<f64> {
+    /// Summarizes the result of packing into this packer, such as by `pack_global`, with its
+    /// bounds as the only bin. Objects are identified by the keys `key` returns, and waste is
+    /// free space too small for `min_size`. `elapsed` is the time the packing took.
+    pub fn report<T,K,F>(&self, result: &Result<PackedResult<T,S>, FailedPacking<T,S>>, mut key: F,
+                         min_size: &Size<S>, elapsed: Duration) -> PackingReport<K,S>
+        where F: FnMut(&T) -> K
+    {
+        let (packed, unpacked) = match *result {
+            Ok(ref packed) => (&packed[..], &[][..]),
+            Err(ref failed) => (failed.packed(), failed.unpacked()),
+        };
+
+        let bins = self.bounds().map(|bounds| BinReport{
+            bounds: bounds.clone(),
+            utilization: self.utilization(),
+            waste: self.waste(min_size),
+        });
+
+        PackingReport{
+            bins: bins.into_iter().collect(),
+            placements: packed.iter().map(|&(ref object, ref rect)| (key(object), 0, rect.clone())).collect(),
+            unpacked: unpacked.iter().map(|x| key(x)).collect(),
+            elapsed: elapsed,
+            stats: self.stats(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use RectPacker;
+    use rect::Rect;
+    use size::Size;
+
+    #[test]
+    fn packing_report() {
+        let mut packer = RectPacker::builder().bin(0,0,10,10).build();
+        let result = packer.pack_global(vec![("a", (10,5)), ("b", (10,4))], |&(_, size)| size);
+
+        let report = packer.report(&result, |&(name, _)| name, &Size::new(1,2), Duration::from_millis(3));
+        assert_eq!(report.placements, vec![("a", 0, Rect::new((0,0), (10,5))), ("b", 0, Rect::new((0,5), (10,9)))]);
+        assert!(report.unpacked.is_empty());
+        assert_eq!(report.bins.len(), 1);
+        assert_eq!(report.bins[0].utilization, 0.9);
+        assert_eq!(report.bins[0].waste, vec![Rect::new((0,9), (10,10))]);
+        assert_eq!((report.elapsed, report.stats.packs_attempted), (Duration::from_millis(3), 2));
+    }
+}
@@ -0,0 +1,124 @@
+use std::ops::{Add, Sub, Mul};
+
+use {RectPacker, PackerConfig, FailedPacking, PackedResult, Rounding, MaybeDebug, MaybeSync};
+use rect::Rect;
+use size::Size;
+use point::Point;
+
+/// A coordinate type with fractions, which can be rounded to whole numbers.
+pub trait Fractional: Sized {
+    /// Rounds to a whole number
+    fn round_with(&self, rounding: Rounding) -> Self;
+}
+
+impl Fractional for f32 {
+    fn round_with(&self, rounding: Rounding) -> f32 {
+        rounding.round(*self as f64) as f32
+    }
+}
+
+impl Fractional for f64 {
+    fn round_with(&self, rounding: Rounding) -> f64 {
+        rounding.round(*self)
+    }
+}
+
+/// A packer with fractional coordinates whose rectangles are all packed at whole coordinates
+/// with whole sizes, e.g. texels, so that callers do not have to round the packed rectangles
+/// themselves, possibly making them overlap.
+///
+/// The sizes of rectangles are rounded as given, which should be `Rounding::Up` unless they may
+/// be cut off. The free rectangles are rounded inwards, and the padding, row pitch and alignment
+/// up. As every free rectangle then starts at whole coordinates, so does every packed rectangle.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RoundedRectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    packer: RectPacker<S>,
+    rounding: Rounding,
+}
+
+impl<S> RoundedRectPacker<S> where S: Fractional + Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    /// Creates a packer with no free rectangles that places rectangles according to `config`,
+    /// rounding their sizes as given
+    pub fn with_config(config: PackerConfig<S>, rounding: Rounding) -> RoundedRectPacker<S> {
+        let config = PackerConfig{
+            padding: config.padding.map(|x| x.round_with(Rounding::Up)),
+            row_pitch: config.row_pitch.map(|x| x.round_with(Rounding::Up)),
+            alignment: config.alignment.map(|x| Size::new(x.width.round_with(Rounding::Up), x.height.round_with(Rounding::Up))),
+            ..config
+        };
+        RoundedRectPacker{packer: RectPacker::with_config(config), rounding: rounding}
+    }
+
+    /// How the sizes of rectangles are rounded
+    #[inline]
+    pub fn rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    /// The packer with the rounded free rectangles
+    #[inline]
+    pub fn packer(&self) -> &RectPacker<S> {
+        &self.packer
+    }
+
+    /// Returns the packer with the rounded free rectangles
+    pub fn into_inner(self) -> RectPacker<S> {
+        self.packer
+    }
+
+    /// Adds a free rectangle as `RectPacker::add_free` does, but rounded inwards to whole
+    /// coordinates. Nothing is added if it holds no whole coordinates.
+    pub fn add_free<A, B>(&mut self, min: A, max: B) where A: Into<Point<S>>, B: Into<Point<S>> {
+        let (min, max) = (min.into(), max.into());
+        let min = Point::new(min.x.round_with(Rounding::Up), min.y.round_with(Rounding::Up));
+        let max = Point::new(max.x.round_with(Rounding::Down), max.y.round_with(Rounding::Down));
+        if min.x <= max.x && min.y <= max.y {
+            self.packer.add_free(min, max);
+        }
+    }
+
+    /// The size of a rectangle of size `size` rounded to whole numbers
+    fn round(&self, size: Size<S>) -> Size<S> {
+        Size::new(size.width.round_with(self.rounding), size.height.round_with(self.rounding))
+    }
+
+    /// Packs a rectangle as `RectPacker::pack` does, with its size rounded
+    pub fn pack(&mut self, width: S, height: S) -> Option<Rect<S>> {
+        let size = self.round(Size::new(width, height));
+        self.packer.pack(size.width, size.height)
+    }
+
+    /// Globally packs objects as `RectPacker::pack_global` does, with their sizes rounded
+    pub fn pack_global<T,F,Z>(&mut self, objects: Vec<T>, mut mapping: F)
+        -> Result<PackedResult<T,S>, FailedPacking<T,S>>
+        where F: for<'a>FnMut(&'a T) -> Z, Z: Into<Size<S>>, S: MaybeSync
+    {
+        let sizes: Vec<Size<S>> = objects.iter().map(|x| self.round(mapping(x).into())).collect();
+        let mut sizes = sizes.into_iter();
+        self.packer.pack_global(objects, |_| sizes.next().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RoundedRectPacker;
+    use {PackerConfig, Rounding};
+    use rect::Rect;
+
+    #[test]
+    fn rounded_pack() {
+        let config = PackerConfig{padding: Some(0.5), ..PackerConfig::default()};
+        let mut packer = RoundedRectPacker::with_config(config, Rounding::Up);
+        packer.add_free((0.5f32, 0.5), (10.7, 10.2));
+        packer.add_free((0.2, 0.2), (0.8, 0.8));
+        assert_eq!(packer.packer().bounds(), Some(&Rect::new((1.0, 1.0), (10.0, 10.0))));
+        assert_eq!(packer.packer().config().padding, Some(1.0));
+
+        assert_eq!(packer.pack(2.3, 2.9), Some(Rect::new((1.0, 1.0), (4.0, 4.0))));
+        let packed = packer.pack_global(vec![(1.1, 0.4), (3.5, 2.0), (0.9, 4.2)], |&x| x).unwrap();
+        for &(_, rect) in packed.iter() {
+            let coordinates = [rect.min.x, rect.min.y, rect.max.x, rect.max.y];
+            assert!(coordinates.iter().all(|x| x.fract() == 0.0));
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use {RectPacker, FailedPacking};
+use rect::Rect;
+use size::Size;
+
+/// How a fractional size is rounded to a whole number of texels.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum Rounding {
+    /// Rounds up, so that nothing is cut off
+    Up,
+    /// Rounds down, so that nothing takes up more space than scaled
+    Down,
+    /// Rounds to the nearest whole number, and halfway cases away from zero
+    Nearest,
+}
+
+impl Default for Rounding {
+    fn default() -> Rounding {
+        Rounding::Up
+    }
+}
+
+impl Rounding {
+    /// Rounds a value to a whole number
+    pub fn round(&self, value: f64) -> f64 {
+        match *self {
+            Rounding::Up => value.ceil(),
+            Rounding::Down => value.floor(),
+            Rounding::Nearest => value.round(),
+        }
+    }
+}
+
+/// Scales a size by `factor`, e.g. 2 to pack assets authored at half the resolution of the
+/// atlas, rounding the width and height as given.
+pub fn scale_size(size: &Size<u32>, factor: f64, rounding: Rounding) -> Size<u32> {
+    let scale = |x: u32| rounding.round(x as f64 * factor) as u32;
+    Size::new(scale(size.width), scale(size.height))
+}
+
+impl RectPacker<u32> {
+    /// Globally packs objects as `pack_global`, but with their sizes scaled by `factor` and
+    /// rounded as by `scale_size`. Every packed object is returned with its size before scaling,
+    /// and its rectangle, which has the scaled size.
+    pub fn pack_global_scaled<T,F,Z>(&mut self, objects: Vec<T>, mut mapping: F, factor: f64, rounding: Rounding)
+        -> Result<Vec<(T, Size<u32>, Rect<u32>)>, FailedPacking<T,u32>>
+        where F: for<'a>FnMut(&'a T) -> Z, Z: Into<Size<u32>>
+    {
+        let sizes: Vec<Size<u32>> = objects.iter().map(|x| mapping(x).into()).collect();
+        let indexed: Vec<(usize, T)> = objects.into_iter().enumerate().collect();
+
+        match self.pack_global(indexed, |&(index, _)| scale_size(&sizes[index], factor, rounding)) {
+            Ok(packed) => Ok(packed.into_iter()
+                .map(|((index, object), rect)| (object, sizes[index].clone(), rect))
+                .collect()),
+            Err(failed) => {
+                let interrupted = failed.interrupted();
+                let (packed, unpacked) = failed.into_parts();
+                Err(FailedPacking{
+                    partial_packed: packed.into_iter().map(|((_, object), rect)| (object, rect)).collect(),
+                    original: unpacked.into_iter().map(|(_, object)| object).collect(),
+                    interrupted: interrupted,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Rounding, scale_size};
+    use RectPacker;
+    use rect::Rect;
+    use size::Size;
+
+    #[test]
+    fn scaled_pack() {
+        assert_eq!(scale_size(&Size::new(5,3), 0.5, Rounding::Up), Size::new(3,2));
+        assert_eq!(scale_size(&Size::new(5,3), 0.5, Rounding::Down), Size::new(2,1));
+        assert_eq!(scale_size(&Size::new(5,3), 0.5, Rounding::Nearest), Size::new(3,2));
+
+        let mut packer = RectPacker::builder().bin(0,0,20,10).build();
+        let packed = packer.pack_global_scaled(vec!["a", "b"], |_| (5,5), 2.0, Rounding::Up).unwrap();
+        assert_eq!(packed, vec![("a", Size::new(5,5), Rect::new((0,0), (10,10))),
+                                ("b", Size::new(5,5), Rect::new((10,0), (20,10)))]);
+
+        let failed = packer.pack_global_scaled(vec!["c"], |_| (1,1), 1.0, Rounding::Up).unwrap_err();
+        assert_eq!(failed.unpacked(), &["c"]);
+    }
+}
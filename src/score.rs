@@ -0,0 +1,203 @@
+use std::cmp::Ordering;
+use std::ops::{Add, Sub, Mul};
+
+use {RectPacker, FailedPacking, PackedResult, MaybeDebug};
+use rect::Rect;
+use size::Size;
+
+/// The quality of a packing, as computed by `score`. Scores are ordered so that the greater
+/// score is the better packing: the one leaving the fewest rectangles unpacked, then using the
+/// fewest bins, then with the lowest height, and then with the highest utilization.
+#[derive(Clone, Copy, Debug)]
+pub struct PackingScore {
+    /// The number of rectangles that were not packed
+    pub unpacked: usize,
+    /// The number of bins holding at least one packed rectangle
+    pub bins_used: usize,
+    /// The greatest distance from the top of a bin to the bottom of a rectangle packed into it,
+    /// which is the height used when packing into a strip
+    pub height: f64,
+    /// The fraction of the area of the bins used that is covered by packed rectangles
+    pub utilization: f64,
+}
+
+/// Scores are equal when neither is the better packing, so that equality agrees with the order.
+impl PartialEq for PackingScore {
+    fn eq(&self, other: &PackingScore) -> bool {
+        self.compare(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for PackingScore {
+    fn partial_cmp(&self, other: &PackingScore) -> Option<Ordering> {
+        Some(self.compare(other))
+    }
+}
+
+impl PackingScore {
+    /// Orders this score relative to another, treating a height or utilization that is not a
+    /// number as equal to any other
+    fn compare(&self, other: &PackingScore) -> Ordering {
+        let compare = |a: f64, b: f64| a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+
+        other.unpacked.cmp(&self.unpacked)
+            .then(other.bins_used.cmp(&self.bins_used))
+            .then(compare(other.height, self.height))
+            .then(compare(self.utilization, other.utilization))
+    }
+
+    /// The estimated optimality gap in bins: the fraction of the bins used that an optimal
+    /// packing might do without, given a lower bound on the number of bins such as by
+    /// `bins_lower_bound`. Zero means the packing is known to be optimal. Only meaningful when
+    /// every rectangle was packed.
+    pub fn bins_gap(&self, lower_bound: usize) -> f64 {
+        if self.bins_used == 0 {
+            return 0.0;
+        }
+        self.bins_used.saturating_sub(lower_bound) as f64 / self.bins_used as f64
+    }
+
+    /// The estimated optimality gap in height, when packing into a strip: the fraction of the
+    /// height used that an optimal packing might do without, given a lower bound on the height
+    /// such as by `height_lower_bound`.
+    pub fn height_gap(&self, lower_bound: f64) -> f64 {
+        if self.height <= 0.0 {
+            return 0.0;
+        }
+        f64::max(self.height - lower_bound, 0.0) / self.height
+    }
+}
+
+/// A lower bound on the number of bins of size `bin` that rectangles of the given sizes can be
+/// packed into without rotation, which every rectangle is assumed to fit in. It is the best of
+/// the bound by area, the number of rectangles larger than half the bin in both directions, which
+/// cannot share a bin, and the bounds by area after transforming the sides by the dual feasible
+/// functions of Fekete and Schepers.
+pub fn bins_lower_bound(bin: &Size<u32>, sizes: &[Size<u32>]) -> usize {
+    // Sides are scaled in u64 and areas summed in f64, as the products overflow u32 for large
+    // sheets
+    let large = sizes.iter().filter(|x| x.width as u64 * 2 > bin.width as u64 && x.height as u64 * 2 > bin.height as u64).count();
+
+    // The function with the parameter `k`, where zero stands for the identity. It maps sides so
+    // that sides fitting next to each other in the bin still do.
+    let dual = |x: u32, capacity: u32, k: u64| -> f64 {
+        let (x, capacity) = (x as u64, capacity as u64);
+        if k == 0 || ((k + 1) * x) % capacity == 0 {
+            x as f64
+        } else {
+            ((k + 1) * x / capacity) as f64 * capacity as f64 / k as f64
+        }
+    };
+
+    let mut bound = large;
+    for k in 0..8 {
+        for l in 0..8 {
+            let area: f64 = sizes.iter().map(|x| dual(x.width, bin.width, k) * dual(x.height, bin.height, l)).sum();
+            let bins = (area / (bin.width as f64 * bin.height as f64) - 1e-9).ceil();
+            bound = ::std::cmp::max(bound, bins as usize);
+        }
+    }
+    bound
+}
+
+/// A lower bound on the height of a strip of width `width` that rectangles of the given sizes can
+/// be packed into without rotation: the greater of the height by area and the tallest rectangle.
+pub fn height_lower_bound(width: u32, sizes: &[Size<u32>]) -> f64 {
+    let area: f64 = sizes.iter().map(|x| x.width as f64 * x.height as f64).sum();
+    sizes.iter().fold((area / width as f64).ceil(), |bound, x| f64::max(bound, x.height as f64))
+}
+
+/// Scores a packing into several bins, given the bins, the index of the bin and the rectangle of
+/// every packed rectangle, and the number of rectangles that were not packed.
+///
+/// # Panics
+///
+/// Panics if a bin index is out of range
+pub fn score<S>(bins: &[Rect<S>], packed: &[(usize, Rect<S>)], unpacked: usize) -> PackingScore
+    where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + Into<f64>
+{
+    let mut used = vec![false; bins.len()];
+    let (mut height, mut packed_area) = (0.0, 0.0);
+    for &(bin, ref rect) in packed.iter() {
+        used[bin] = true;
+        height = f64::max(height, (rect.max.y.clone() - bins[bin].min.y.clone()).into());
+        packed_area += rect.area().into();
+    }
+
+    let bin_area: f64 = bins.iter().zip(used.iter()).filter(|x| *x.1).map(|(x, _)| x.area().into()).sum();
+    PackingScore{
+        unpacked: unpacked,
+        bins_used: used.iter().filter(|&&x| x).count(),
+        height: height,
+        utilization: if bin_area > 0.0 { packed_area / bin_area } else { 0.0 },
+    }
+}
+
+impl<S> RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug + Into<f64> {
+    /// Scores the result of packing into this packer, such as by `pack_global`, with its bounds
+    /// as the only bin. Without bounds, nothing can have been packed.
+    pub fn score<T>(&self, result: &Result<PackedResult<T,S>, FailedPacking<T,S>>) -> PackingScore {
+        let (packed, unpacked) = match *result {
+            Ok(ref packed) => (&packed[..], 0),
+            Err(ref failed) => (failed.packed(), failed.unpacked().len()),
+        };
+
+        let bins: Vec<Rect<S>> = self.bounds().into_iter().cloned().collect();
+        let packed: Vec<(usize, Rect<S>)> = packed.iter().map(|&(_, ref rect)| (0, rect.clone())).collect();
+        score(&bins, &packed, unpacked)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cmp::Ordering;
+
+    use super::{PackingScore, score, bins_lower_bound, height_lower_bound};
+    use RectPacker;
+    use rect::Rect;
+    use size::Size;
+
+    #[test]
+    fn packing_score() {
+        let bins = [Rect::new((0,0), (10,10)), Rect::new((0,20), (10,30))];
+        let one = score(&bins, &[(1, Rect::new((0,20), (10,25)))], 0);
+        assert_eq!((one.bins_used, one.height, one.utilization), (1, 5.0, 0.5));
+
+        let two = score(&bins, &[(0, Rect::new((0,0), (10,5))), (1, Rect::new((0,20), (10,25)))], 0);
+        let taller = score(&bins, &[(1, Rect::new((0,22), (10,27)))], 0);
+        let failed = score(&bins, &[], 1);
+        assert!(one > two && one > taller && two > failed && taller > failed);
+
+        // A utilization that is not a number ties with any other, and equality agrees
+        let unknown = PackingScore{utilization: ::std::f64::NAN, ..one};
+        assert!(unknown == unknown && unknown.partial_cmp(&unknown) == Some(Ordering::Equal));
+        assert!(unknown == one && !(unknown < one) && !(unknown > one));
+
+        let mut packer = RectPacker::builder().bin(0,0,10,10).build();
+        let result = packer.pack_global(vec![(5,5), (10,10)], |&x| x);
+        assert_eq!(packer.score(&result).unpacked, 1);
+    }
+
+    #[test]
+    fn optimality_gap() {
+        let bin = Size::new(10,10);
+        assert_eq!(bins_lower_bound(&bin, &[]), 0);
+        assert_eq!(bins_lower_bound(&bin, &[Size::new(10,10)]), 1);
+        assert_eq!(bins_lower_bound(&bin, &vec![Size::new(6,6); 3]), 3);
+        assert_eq!(bins_lower_bound(&bin, &vec![Size::new(4,10); 5]), 3);
+        assert_eq!(bins_lower_bound(&bin, &vec![Size::new(5,5); 8]), 2);
+
+        let sheet = Size::new(4_000_000_000, 4_000_000_000);
+        assert_eq!(bins_lower_bound(&sheet, &vec![Size::new(2_500_000_000, 2_500_000_000); 3]), 3);
+        assert_eq!(bins_lower_bound(&sheet, &vec![Size::new(2_000_000_000, 4_000_000_000); 5]), 3);
+
+        let bins = [Rect::new((0,0), (10,10)), Rect::new((0,0), (10,10))];
+        let two = score(&bins, &[(0, Rect::new((0,0), (5,5))), (1, Rect::new((0,0), (5,5)))], 0);
+        assert_eq!(two.bins_gap(1), 0.5);
+        assert_eq!(two.bins_gap(2), 0.0);
+
+        let sizes = [Size::new(10,5), Size::new(5,2)];
+        assert_eq!(height_lower_bound(10, &sizes), 6.0);
+        assert_eq!(score(&bins[..1], &[(0, Rect::new((0,0), (10,8)))], 0).height_gap(6.0), 0.25);
+    }
+}
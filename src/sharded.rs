@@ -0,0 +1,136 @@
+use std::ops::{Add, Sub, Mul};
+use std::sync::Mutex;
+
+use {RectPacker, PackerConfig, MaybeDebug};
+use rect::Rect;
+use size::Size;
+
+/// A packer for many threads packing at once, with the free space partitioned into shards that
+/// are packed independently, so that threads packing into different shards never wait for each
+/// other. Each thread would normally pack into a shard of its own.
+///
+/// When a rectangle does not fit in its shard, a free rectangle it fits in is stolen from another
+/// shard and moved to its shard, along with the space around it. Only when no shard has such a
+/// free rectangle does packing fail. The bounds of every shard stay those of its region, so
+/// rectangles packed into stolen space lie outside the bounds of the shard they were packed by.
+#[derive(Debug)]
+pub struct ShardedRectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    shards: Vec<Mutex<RectPacker<S>>>,
+}
+
+impl<S> ShardedRectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    /// Creates a packer with a shard for every region, each packed with `config`. The regions
+    /// should not overlap.
+    pub fn new(config: PackerConfig<S>, regions: Vec<Rect<S>>) -> ShardedRectPacker<S> {
+        ShardedRectPacker{shards: regions.into_iter().map(|region| {
+            let mut shard = RectPacker::with_config(config.clone());
+            shard.add_free(region.min, region.max);
+            Mutex::new(shard)
+        }).collect()}
+    }
+
+    /// The number of shards
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the packer of every shard
+    pub fn into_shards(self) -> Vec<RectPacker<S>> {
+        self.shards.into_iter().map(|x| x.into_inner().unwrap()).collect()
+    }
+
+    /// Packs a rectangle into the shard with index `shard`, stealing free space from the other
+    /// shards if it does not fit, and returns it. Returns `None` if it fits in no shard.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no shard with index `shard`
+    pub fn pack(&self, shard: usize, width: S, height: S) -> Option<Rect<S>> {
+        loop {
+            if let Some(rect) = self.shards[shard].lock().unwrap().pack(width.clone(), height.clone()) {
+                return Some(rect);
+            }
+
+            let stolen = (1..self.shards.len())
+                .map(|offset| (shard + offset) % self.shards.len())
+                .filter_map(|victim| self.steal(victim, &Size::new(width.clone(), height.clone())))
+                .next();
+
+            match stolen {
+                Some(free) => {
+                    debug!("shard {} stole {:?}", shard, free);
+                    let mut packer = self.shards[shard].lock().unwrap();
+                    packer.insert_free(free);
+                    packer.truncate_free();
+                    packer.update_peak();
+                    packer.refresh_cache();
+                }
+                None => return None,
+            }
+        }
+    }
+
+    /// Removes the largest free rectangle that a rectangle of size `size` fits in from a shard
+    /// and returns it, or returns `None` if there is none. Only the shard is locked, so that
+    /// shards stealing from each other cannot deadlock.
+    fn steal(&self, victim: usize, size: &Size<S>) -> Option<Rect<S>> {
+        let mut packer = self.shards[victim].lock().unwrap();
+        let mut sizes = vec![packer.config().padded_size(size)];
+        if packer.config().allow_rotation {
+            sizes.push(packer.config().padded_size(&Size::new(size.height.clone(), size.width.clone())));
+        }
+
+        let mut best: Option<Rect<S>> = None;
+        for free in packer.free_rects().iter() {
+            let fits = sizes.iter().any(|x| x.width <= free.width() && x.height <= free.height());
+            if fits && best.as_ref().map_or(true, |best| free.area() > best.area()) {
+                best = Some(free.clone());
+            }
+        }
+
+        if let Some(ref free) = best {
+            packer.subtract_rect(free);
+            packer.refresh_cache();
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use super::ShardedRectPacker;
+    use PackerConfig;
+    use rect::Rect;
+
+    #[test]
+    fn sharded_pack() {
+        let regions = vec![Rect::new((0,0), (10,10)), Rect::new((10,0), (20,10))];
+        let packer = ShardedRectPacker::new(PackerConfig::default(), regions);
+
+        assert_eq!(packer.pack(0, 10,10), Some(Rect::new((0,0), (10,10))));
+        assert_eq!(packer.pack(0, 5,5), Some(Rect::new((10,0), (15,5))));
+        assert_eq!(packer.pack(1, 5,5), Some(Rect::new((10,5), (15,10))));
+        assert_eq!(packer.pack(1, 6,6), None);
+
+        let shards = packer.into_shards();
+        assert_eq!(shards[0].bounds(), Some(&Rect::new((0,0), (10,10))));
+        assert_eq!(shards[1].bounds(), Some(&Rect::new((10,0), (20,10))));
+
+        let regions = (0..4).map(|x| Rect::new((x * 25, 0), (x * 25 + 25, 100))).collect();
+        let packer = ShardedRectPacker::new(PackerConfig::default(), regions);
+        let packed: Vec<_> = thread::scope(|scope| {
+            let packer = &packer;
+            let threads: Vec<_> = (0..4).map(|shard| scope.spawn(move || {
+                (0..10 + shard * 5).map(|_| packer.pack(shard, 10,10).unwrap()).collect::<Vec<_>>()
+            })).collect();
+            threads.into_iter().flat_map(|x| x.join().unwrap()).collect()
+        });
+
+        assert_eq!(packed.len(), 70);
+        for (i, a) in packed.iter().enumerate() {
+            assert!(packed[i + 1..].iter().all(|b| !a.intersects(b)));
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use std::ops::{Add, Sub, Mul};
+
+use {RectPacker, FailedPacking, Operation, MaybeDebug, MaybeSync};
+use size::Size;
+use point::Point;
+
+/// A consumer of packed objects, which `RectPacker::pack_global_into` passes every object to as
+/// soon as it is packed, e.g. to write huge packings straight to a file or a command list
+/// without buffering them. Closures taking the same arguments as `place` are sinks.
+pub trait PlacementSink<T,S> {
+    /// Receives a packed object, the position of the minimum corner of its rectangle and its
+    /// size, with the width and height swapped if it was rotated
+    fn place(&mut self, item: T, position: Point<S>, size: Size<S>);
+}
+
+impl<T,S,F> PlacementSink<T,S> for F where F: FnMut(T, Point<S>, Size<S>) {
+    fn place(&mut self, item: T, position: Point<S>, size: Size<S>) {
+        self(item, position, size)
+    }
+}
+
+impl<S> RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug + MaybeSync {
+    /// Globally packs objects as `pack_global`, but passes every packed object to `sink` as soon
+    /// as it is packed rather than returning them. If not every object is packed, the failed
+    /// packing holds only the objects that were not.
+    pub fn pack_global_into<T,F,Z,K>(&mut self, objects: Vec<T>, mut mapping: F, sink: &mut K)
+        -> Result<(), FailedPacking<T,S>>
+        where F: for<'a>FnMut(&'a T) -> Z, Z: Into<Size<S>>, K: PlacementSink<T,S>
+    {
+        let sizes: Vec<Size<S>> = objects.iter().map(|x| mapping(x).into()).collect();
+        let recorded = self.recording.as_ref().map(|_| sizes.clone());
+
+        let count = objects.len();
+        let oriented = sizes.into_iter().map(|x| (x, true)).collect();
+        let result = self.pack_global_streamed(objects, oriented, &mut |object, rect| {
+            let size = rect.size();
+            sink.place(object, rect.min, size)
+        }, |_| (), || false);
+
+        if let Some(sizes) = recorded {
+            let placements = count - result.as_ref().err().map_or(0, |x| x.unpacked().len());
+            self.record(Operation::PackGlobal(sizes, placements));
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use RectPacker;
+    use point::Point;
+    use size::Size;
+
+    #[test]
+    fn streamed_pack() {
+        let mut packer = RectPacker::builder().bin(0,0,10,10).build();
+        let mut lines = Vec::new();
+        packer.pack_global_into(vec!["a", "b"], |_| (5,10), &mut |name, position: Point<u32>, size: Size<u32>| {
+            lines.push(format!("{} {} {} {} {}", name, position.x, position.y, size.width, size.height));
+        }).unwrap();
+        assert_eq!(lines, vec!["a 0 0 5 10", "b 5 0 5 10"]);
+
+        let mut placed = 0;
+        let failed = packer.pack_global_into(vec![1, 2], |_| (1,1), &mut |_, _, _| placed += 1).unwrap_err();
+        assert_eq!((placed, failed.packed().len(), failed.unpacked().len()), (0, 0, 2));
+    }
+}
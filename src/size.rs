@@ -0,0 +1,178 @@
+use std::ops::Sub;
+
+use rect::Rect;
+
+/// The dimensions of a rectangle given by its width and height
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Size<S> {
+    pub width: S,
+    pub height: S,
+}
+
+impl<S> Size<S> {
+    /// Creates a size from a width and a height
+    pub fn new(width: S, height: S) -> Size<S> {
+        Size{width: width, height: height}
+    }
+}
+
+impl<S> Size<S> where S: Clone + PartialEq + Sub<S, Output=S> {
+    /// Whether the width or the height is zero
+    pub fn is_empty(&self) -> bool {
+        // Subtracting a value from itself gives zero for any scalar
+        let zero = self.width.clone() - self.width.clone();
+        self.width == zero || self.height == zero
+    }
+
+    /// Whether the width or the height is negative
+    pub fn is_negative(&self) -> bool where S: PartialOrd {
+        let zero = self.width.clone() - self.width.clone();
+        self.width < zero || self.height < zero
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl<S> ::quickcheck::Arbitrary for Size<S> where S: ::quickcheck::Arbitrary {
+    fn arbitrary(g: &mut ::quickcheck::Gen) -> Size<S> {
+        Size::new(S::arbitrary(g), S::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item=Size<S>>> {
+        Box::new((self.width.clone(), self.height.clone()).shrink().map(|(width, height)| Size::new(width, height)))
+    }
+}
+
+impl<S> From<(S,S)> for Size<S> {
+    fn from((width, height): (S,S)) -> Size<S> {
+        Size::new(width, height)
+    }
+}
+
+impl<S> From<Size<S>> for (S,S) {
+    fn from(size: Size<S>) -> (S,S) {
+        (size.width, size.height)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<S> From<::mint::Vector2<S>> for Size<S> {
+    fn from(size: ::mint::Vector2<S>) -> Size<S> {
+        Size::new(size.x, size.y)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<S> From<Size<S>> for ::mint::Vector2<S> {
+    fn from(size: Size<S>) -> ::mint::Vector2<S> {
+        ::mint::Vector2{x: size.width, y: size.height}
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<S, U> From<::euclid::Size2D<S, U>> for Size<S> {
+    fn from(size: ::euclid::Size2D<S, U>) -> Size<S> {
+        Size::new(size.width, size.height)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<S, U> From<Size<S>> for ::euclid::Size2D<S, U> {
+    fn from(size: Size<S>) -> ::euclid::Size2D<S, U> {
+        ::euclid::Size2D::new(size.width, size.height)
+    }
+}
+
+// glam vectors are converted for, and are size sources of, the scalar type matching their
+// components
+#[cfg(feature = "glam")]
+macro_rules! glam_conversions {
+    ($($vector:ident: $scalar:ty),*) => ($(
+        impl From<::glam::$vector> for Size<$scalar> {
+            fn from(size: ::glam::$vector) -> Size<$scalar> {
+                Size::new(size.x, size.y)
+            }
+        }
+
+        impl From<Size<$scalar>> for ::glam::$vector {
+            fn from(size: Size<$scalar>) -> ::glam::$vector {
+                ::glam::$vector::new(size.width, size.height)
+            }
+        }
+
+        impl SizeSource<$scalar> for ::glam::$vector {
+            fn rect_size(&self) -> Size<$scalar> {
+                Size::new(self.x, self.y)
+            }
+        }
+    )*)
+}
+
+#[cfg(feature = "glam")]
+glam_conversions!(Vec2: f32, IVec2: i32, UVec2: u32);
+
+/// Something with a rectangular size of its own, such as an image, that can be packed without
+/// mapping it to its size, e.g. by `RectPacker::pack_global_sized`.
+pub trait SizeSource<S> {
+    /// The size of the rectangle to pack it into
+    fn rect_size(&self) -> Size<S>;
+}
+
+impl<S> SizeSource<S> for Size<S> where S: Clone {
+    fn rect_size(&self) -> Size<S> {
+        self.clone()
+    }
+}
+
+impl<S> SizeSource<S> for (S,S) where S: Clone {
+    fn rect_size(&self) -> Size<S> {
+        Size::new(self.0.clone(), self.1.clone())
+    }
+}
+
+impl<S> SizeSource<S> for [S; 2] where S: Clone {
+    fn rect_size(&self) -> Size<S> {
+        Size::new(self[0].clone(), self[1].clone())
+    }
+}
+
+impl<S> SizeSource<S> for Rect<S> where S: Clone + Sub<S, Output=S> {
+    fn rect_size(&self) -> Size<S> {
+        self.size()
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<S> SizeSource<S> for ::mint::Vector2<S> where S: Clone {
+    fn rect_size(&self) -> Size<S> {
+        Size::new(self.x.clone(), self.y.clone())
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<S, U> SizeSource<S> for ::euclid::Size2D<S, U> where S: Clone {
+    fn rect_size(&self) -> Size<S> {
+        Size::new(self.width.clone(), self.height.clone())
+    }
+}
+
+impl<'a, S, T> SizeSource<S> for &'a T where T: SizeSource<S> + ?Sized {
+    fn rect_size(&self) -> Size<S> {
+        (**self).rect_size()
+    }
+}
+
+#[cfg(feature = "image")]
+impl SizeSource<u32> for ::image::DynamicImage {
+    fn rect_size(&self) -> Size<u32> {
+        Size::new(self.width(), self.height())
+    }
+}
+
+#[cfg(feature = "image")]
+impl<P, C> SizeSource<u32> for ::image::ImageBuffer<P, C> where P: ::image::Pixel, C: ::std::ops::Deref<Target=[P::Subpixel]> {
+    fn rect_size(&self) -> Size<u32> {
+        Size::new(self.width(), self.height())
+    }
+}
@@ -0,0 +1,119 @@
+use {RectPacker, PackerConfig, ZeroSizePolicy};
+use rect::Rect;
+use point::Point;
+use size::Size;
+
+/// Checks at compile time that a rectangle of `W2` by `H2` fits in a bin of `W` by `H`.
+struct FitsBin<const W: u32, const H: u32, const W2: u32, const H2: u32>;
+
+impl<const W: u32, const H: u32, const W2: u32, const H2: u32> FitsBin<W, H, W2, H2> {
+    const CHECK: () = assert!(W2 <= W && H2 <= H, "the rectangle is larger than the bin");
+}
+
+/// A packer for a single bin of `W` by `H` at the origin, such as a fixed-size atlas, whose size
+/// is part of its type.
+///
+/// The bounds follow from the type rather than being kept by the packer, and rectangles whose size
+/// is known at compile time can be packed with `pack_sized`, which fails to compile if they are
+/// larger than the bin.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StaticRectPacker<const W: u32, const H: u32> {
+    packer: RectPacker<u32>,
+}
+
+impl<const W: u32, const H: u32> StaticRectPacker<W, H> {
+    /// The bin, from the origin to `W` by `H`
+    pub const BOUNDS: Rect<u32> = Rect{min: Point{x: 0, y: 0}, max: Point{x: W, y: H}};
+
+    /// Creates a packer with the whole bin free and the default options
+    pub fn new() -> StaticRectPacker<W, H> {
+        StaticRectPacker::with_config(PackerConfig::default())
+    }
+
+    /// Creates a packer with the whole bin free that places rectangles according to `config`
+    pub fn with_config(config: PackerConfig<u32>) -> StaticRectPacker<W, H> {
+        let mut packer = RectPacker::with_config(config);
+        packer.insert_free(Self::BOUNDS);
+        packer.update_peak();
+        packer.refresh_cache();
+        StaticRectPacker{packer: packer}
+    }
+
+    /// The bin, as `BOUNDS`
+    #[inline]
+    pub fn bounds(&self) -> Rect<u32> {
+        Self::BOUNDS
+    }
+
+    /// The packer of the bin, which has no bounds of its own
+    #[inline]
+    pub fn packer(&self) -> &RectPacker<u32> {
+        &self.packer
+    }
+
+    /// Returns the packer of the bin, with `BOUNDS` as its bounds
+    pub fn into_inner(self) -> RectPacker<u32> {
+        let mut packer = self.packer;
+        packer.bounds = Some(Self::BOUNDS);
+        packer
+    }
+
+    /// Packs a rectangle as `RectPacker::pack` does. Rectangles larger than the bin are rejected
+    /// without searching the free rectangles.
+    pub fn pack(&mut self, width: u32, height: u32) -> Option<Rect<u32>> {
+        let fits = (width <= W && height <= H) ||
+            (self.packer.config().allow_rotation && height <= W && width <= H);
+        if !fits {
+            return None;
+        }
+
+        self.place(width, height)
+    }
+
+    /// Packs a rectangle of `WIDTH` by `HEIGHT` as `pack` does. Fails to compile if it is larger
+    /// than the bin.
+    pub fn pack_sized<const WIDTH: u32, const HEIGHT: u32>(&mut self) -> Option<Rect<u32>> {
+        let () = FitsBin::<W, H, WIDTH, HEIGHT>::CHECK;
+        self.place(WIDTH, HEIGHT)
+    }
+
+    /// Packs a rectangle into the packer. Without bounds, the packer cannot place an empty
+    /// rectangle at their minimum under `ZeroSizePolicy::Origin`, so it is placed at the origin
+    /// here.
+    fn place(&mut self, width: u32, height: u32) -> Option<Rect<u32>> {
+        let placed = self.packer.pack(width, height);
+        let size = Size::new(width, height);
+        if placed.is_none() && size.is_empty() && self.packer.config().zero_size == ZeroSizePolicy::Origin {
+            return Some(Rect::with_size(Self::BOUNDS.min, size));
+        }
+        placed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::StaticRectPacker;
+    use {PackerConfig, ZeroSizePolicy};
+    use rect::Rect;
+
+    #[test]
+    fn static_pack() {
+        let mut packer = StaticRectPacker::<16, 8>::new();
+        assert_eq!(packer.bounds(), Rect::new((0,0), (16,8)));
+        assert_eq!(packer.packer().bounds(), None);
+
+        assert_eq!(packer.pack(8,16), None);
+        assert_eq!(packer.pack_sized::<16, 4>(), Some(Rect::new((0,0), (16,4))));
+        assert_eq!(packer.pack(8,4), Some(Rect::new((0,4), (8,8))));
+        assert_eq!(packer.pack_sized::<8, 8>(), None);
+        let packer = packer.into_inner();
+        assert_eq!(packer.free_rects(), &[Rect::new((8,4), (16,8))][..]);
+        assert_eq!(packer.bounds(), Some(&Rect::new((0,0), (16,8))));
+
+        let config = PackerConfig{zero_size: ZeroSizePolicy::Origin, ..PackerConfig::default()};
+        let mut packer = StaticRectPacker::<16, 8>::with_config(config);
+        assert_eq!(packer.pack(0,4), Some(Rect::new((0,0), (0,4))));
+        assert_eq!(packer.pack_sized::<4, 0>(), Some(Rect::new((0,0), (4,0))));
+        assert_eq!(packer.packer().free_rects(), &[Rect::new((0,0), (16,8))][..]);
+    }
+}
@@ -0,0 +1,104 @@
+//! Strategies for property-testing code that uses a packer with `proptest`, generating valid
+//! bins, rectangle sizes, options and operation sequences with `u32` coordinates, and a check of
+//! the invariants every packing must uphold.
+
+use std::fmt::Debug;
+use std::ops::Range;
+
+use proptest::prelude::*;
+use proptest::collection::vec;
+use proptest::option;
+use proptest::sample::select;
+
+use {Heuristic, PackerConfig, ZeroSizePolicy, Operation, Grain};
+use rect::Rect;
+use size::Size;
+
+/// Sizes with a width and a height in `1..max`
+pub fn sizes(max: u32) -> impl Strategy<Value=Size<u32>> {
+    (1..max, 1..max).prop_map(|(width, height)| Size::new(width, height))
+}
+
+/// Sets of `count` sizes, as by `sizes`
+pub fn size_sets(max: u32, count: Range<usize>) -> impl Strategy<Value=Vec<Size<u32>>> {
+    vec(sizes(max), count)
+}
+
+/// Non-empty bins starting in `0..max` and extending less than `max` from there
+pub fn bins(max: u32) -> impl Strategy<Value=Rect<u32>> {
+    (0..max, 0..max, sizes(max)).prop_map(|(x, y, size)| Rect::with_size((x, y), size))
+}
+
+/// Options with small padding, row pitches and alignments
+pub fn configs() -> impl Strategy<Value=PackerConfig<u32>> {
+    let heuristics = select(vec![Heuristic::Bssf, Heuristic::Blsf, Heuristic::Baf, Heuristic::BottomLeft]);
+    let zero_sizes = select(vec![ZeroSizePolicy::Pack, ZeroSizePolicy::Reject, ZeroSizePolicy::Skip, ZeroSizePolicy::Origin]);
+    (heuristics, any::<bool>(), option::of(0..4u32), option::of(1..8u32),
+     option::of((1..8u32, 1..8u32)), option::of(any::<u64>()), zero_sizes)
+        .prop_map(|(heuristic, allow_rotation, padding, row_pitch, alignment, tie_seed, zero_size)| PackerConfig{
+            heuristic: heuristic,
+            allow_rotation: allow_rotation,
+            padding: padding,
+            row_pitch: row_pitch,
+            alignment: alignment.map(|(width, height)| Size::new(width, height)),
+            tie_seed: tie_seed,
+            zero_size: zero_size,
+        })
+}
+
+/// Sequences of `count` operations, which can be replayed by `RectPacker::replay`, with bins and
+/// sizes as by `bins` and `sizes`
+pub fn operations(max: u32, count: Range<usize>) -> impl Strategy<Value=Vec<Operation<u32>>> {
+    let operation = prop_oneof![
+        bins(max).prop_map(Operation::AddFree),
+        bins(max).prop_map(|bin| Operation::GrowTo(bin.max)),
+        sizes(max).prop_map(Operation::Pack),
+        (sizes(max), bins(max)).prop_map(|(size, near)| Operation::PackNear(size, near)),
+        (size_sets(max, 0..8), 0..8usize).prop_map(|(sizes, placements)| Operation::PackGlobal(sizes, placements)),
+        (vec((sizes(max), select(vec![Grain::Any, Grain::Width, Grain::Height])), 0..8), 0..8usize)
+            .prop_map(|(items, placements)| Operation::PackGlobalWithGrain(items, placements)),
+        option::of(1..64usize).prop_map(Operation::SetMaxFree),
+        configs().prop_map(Operation::SetConfig),
+    ];
+    vec(operation, count)
+}
+
+/// Asserts that every packed rectangle lies within one of the bins and that no two packed
+/// rectangles intersect.
+///
+/// # Panics
+///
+/// Panics, naming the offending rectangles, if either does not hold
+pub fn assert_valid_packing<S>(bins: &[Rect<S>], packed: &[Rect<S>]) where S: PartialOrd + Debug {
+    for (index, rect) in packed.iter().enumerate() {
+        assert!(bins.iter().any(|bin| bin.supersets(rect)), "{:?} is not within any bin", rect);
+
+        if let Some(other) = packed[index + 1..].iter().find(|other| other.intersects(rect)) {
+            panic!("{:?} intersects {:?}", rect, other);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::{bins, size_sets, operations, assert_valid_packing};
+    use RectPacker;
+
+    proptest! {
+        #[test]
+        fn valid_packing(bin in bins(64), sizes in size_sets(16, 0..32)) {
+            let mut packer = RectPacker::new();
+            packer.add_free(bin.min, bin.max);
+
+            let packed: Vec<_> = packer.pack_many(&sizes).into_iter().filter_map(|x| x).collect();
+            assert_valid_packing(&[bin], &packed);
+        }
+
+        #[test]
+        fn replayed_operations(operations in operations(64, 0..16)) {
+            RectPacker::new().replay(&operations);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use rect::Rect;
+
+/// The arguments of one `wgpu::Queue::write_texture` call, which uploads the pixels of a packed
+/// rectangle to the same rectangle of an atlas texture.
+#[derive(Clone, Copy, Debug)]
+pub struct TextureUpload {
+    /// Where the rectangle starts in the texture
+    pub origin: ::wgpu::Origin3d,
+    /// The size of the rectangle
+    pub extent: ::wgpu::Extent3d,
+    /// The layout of the rectangle's pixels in its source bytes
+    pub layout: ::wgpu::TexelCopyBufferLayout,
+}
+
+impl TextureUpload {
+    /// The upload of a packed rectangle whose source bytes are rows of `bytes_per_pixel` bytes
+    /// per pixel, one right after another.
+    ///
+    /// The pixels are copied as they are, so the source of a rectangle that was packed rotated
+    /// must already be rotated.
+    pub fn new(rect: &Rect<u32>, bytes_per_pixel: u32) -> TextureUpload {
+        TextureUpload{
+            origin: ::wgpu::Origin3d{x: rect.min.x, y: rect.min.y, z: 0},
+            extent: ::wgpu::Extent3d{width: rect.width(), height: rect.height(), depth_or_array_layers: 1},
+            layout: ::wgpu::TexelCopyBufferLayout{
+                offset: 0,
+                bytes_per_row: Some(rect.width() * bytes_per_pixel),
+                rows_per_image: None,
+            },
+        }
+    }
+
+    /// Uploads `data` to the first mip level of `texture` through `queue`
+    pub fn write(&self, queue: &::wgpu::Queue, texture: &::wgpu::Texture, data: &[u8]) {
+        let destination = ::wgpu::TexelCopyTextureInfo{
+            texture: texture,
+            mip_level: 0,
+            origin: self.origin,
+            aspect: ::wgpu::TextureAspect::All,
+        };
+
+        queue.write_texture(destination, data, self.layout, self.extent);
+    }
+}
+
+/// Uploads every packed object to its rectangle of `texture` through `queue`, with its source
+/// bytes given by `data` as for `TextureUpload::new`.
+pub fn write_packed<T,F>(queue: &::wgpu::Queue, texture: &::wgpu::Texture, packed: &[(T, Rect<u32>)],
+                         bytes_per_pixel: u32, mut data: F)
+    where F: FnMut(&T) -> &[u8]
+{
+    for &(ref object, ref rect) in packed.iter() {
+        TextureUpload::new(rect, bytes_per_pixel).write(queue, texture, data(object));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TextureUpload;
+    use rect::Rect;
+
+    #[test]
+    fn texture_upload() {
+        let upload = TextureUpload::new(&Rect::new((16,8), (26,12)), 4);
+        assert_eq!(upload.origin, ::wgpu::Origin3d{x: 16, y: 8, z: 0});
+        assert_eq!(upload.extent, ::wgpu::Extent3d{width: 10, height: 4, depth_or_array_layers: 1});
+        assert_eq!((upload.layout.offset, upload.layout.bytes_per_row, upload.layout.rows_per_image), (0, Some(40), None));
+    }
+}
@@ -0,0 +1,148 @@
+use std::ops::{Add, Sub, Mul};
+use std::sync::Arc;
+
+use {RectPacker, PackerConfig, Stats, MaybeDebug};
+use rect::Rect;
+use size::Size;
+use point::Point;
+
+/// A read-only snapshot of a packer, as returned by `RectPacker::view`, for answering queries
+/// from other threads while the packer itself goes on packing.
+///
+/// The free rectangles are shared between clones of a view, so a view is cloned cheaply. It is
+/// `Send` and `Sync` whenever `S` is, and does not change when the packer does.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PackerView<S> {
+    free: Arc<Vec<Rect<S>>>,
+    bounds: Option<Rect<S>>,
+    packed_area: Option<S>,
+    config: PackerConfig<S>,
+    stats: Stats,
+}
+
+impl<S> PackerView<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    /// The free rectangles of the packer when the view was taken
+    #[inline]
+    pub fn free_rects(&self) -> &[Rect<S>] {
+        &self.free
+    }
+
+    /// The bounds of the packer, see `RectPacker::bounds`
+    #[inline]
+    pub fn bounds(&self) -> Option<&Rect<S>> {
+        self.bounds.as_ref()
+    }
+
+    /// The area packed, see `RectPacker::packed_area`
+    #[inline]
+    pub fn packed_area(&self) -> Option<&S> {
+        self.packed_area.as_ref()
+    }
+
+    /// The fraction of the bounds packed, see `RectPacker::utilization`
+    pub fn utilization(&self) -> f64 where S: Into<f64> {
+        match (&self.packed_area, &self.bounds) {
+            (&Some(ref area), &Some(ref bounds)) => area.clone().into() / bounds.area().into(),
+            _ => 0.0,
+        }
+    }
+
+    /// The options of the packer
+    #[inline]
+    pub fn config(&self) -> &PackerConfig<S> {
+        &self.config
+    }
+
+    /// The counters of the packer, see `RectPacker::stats`
+    #[inline]
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Whether a rectangle of the given size, padded as the packer pads it, fits in a free
+    /// rectangle, rotated if the packer may rotate it. An empty size fits as the zero-size policy
+    /// of the packer places it. If so, packing it would succeed.
+    pub fn can_fit(&self, width: S, height: S) -> bool {
+        if let Some(placed) = self.config.place_empty(self.bounds.as_ref(), &Size::new(width.clone(), height.clone())) {
+            return placed.is_some();
+        }
+
+        let mut sizes = vec![self.config.padded_size(&Size::new(width.clone(), height.clone()))];
+        if self.config.allow_rotation {
+            sizes.push(self.config.padded_size(&Size::new(height, width)));
+        }
+
+        self.free.iter().any(|free| sizes.iter().any(|x| x.width <= free.width() && x.height <= free.height()))
+    }
+
+    /// Whether a point is in free space, i.e. within any of the free rectangles
+    pub fn is_free_at<P>(&self, point: P) -> bool where P: Into<Point<S>> {
+        let point = point.into();
+        self.free.iter().any(|x| x.contains(&point))
+    }
+
+    /// The free rectangles that a point is within
+    pub fn free_rects_at<P>(&self, point: P) -> Vec<&Rect<S>> where P: Into<Point<S>> {
+        let point = point.into();
+        self.free.iter().filter(|x| x.contains(&point)).collect()
+    }
+}
+
+impl<S> RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    /// Takes a read-only snapshot of the packer that can be queried from other threads. This
+    /// copies the free rectangles.
+    pub fn view(&self) -> PackerView<S> {
+        PackerView{
+            free: Arc::new(self.empty.clone()),
+            bounds: self.bounds.clone(),
+            packed_area: self.packed_area.clone(),
+            config: self.config.clone(),
+            stats: self.stats,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use super::PackerView;
+    use {RectPacker, PackerConfig, ZeroSizePolicy};
+    use rect::Rect;
+
+    #[test]
+    fn packer_view() {
+        fn shareable<T: Send + Sync>(_: &T) {}
+
+        let mut packer = RectPacker::builder().bin(0,0,10,10).build();
+        packer.pack(10,5);
+        let view: PackerView<u32> = packer.view();
+        shareable(&view);
+
+        packer.pack(10,5);
+        assert_eq!(packer.pack(1,1), None);
+
+        thread::scope(|scope| {
+            let view = &view;
+            scope.spawn(move || {
+                assert!(view.can_fit(10,5));
+                assert!(!view.can_fit(5,10));
+                assert!(view.is_free_at((0,5)));
+                assert!(!view.is_free_at((0,4)));
+                assert_eq!(view.free_rects_at((9,9)), vec![&Rect::new((0,5), (10,10))]);
+                assert_eq!(view.utilization(), 0.5);
+                assert_eq!(view.stats().packs_attempted, 1);
+            });
+        });
+
+        let rotating = RectPacker::builder().bin(0,0,10,5).allow_rotation(true).build().view();
+        assert!(rotating.can_fit(5,10));
+
+        for &(policy, fits) in [(ZeroSizePolicy::Pack, true), (ZeroSizePolicy::Reject, false), (ZeroSizePolicy::Skip, false), (ZeroSizePolicy::Origin, true)].iter() {
+            let config = PackerConfig{zero_size: policy, ..PackerConfig::default()};
+            let mut packer = RectPacker::builder().bin(0,0,10,10).config(config).build();
+            assert_eq!(packer.view().can_fit(0,5), fits);
+            assert_eq!(packer.pack(0,5).is_some(), fits);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::ops::{Add, Sub, Mul};
+
+use {RectPacker, MaybeDebug, sum};
+use rect::Rect;
+use size::Size;
+use point::Point;
+
+impl<S> RectPacker<S> where S: Clone + PartialOrd + Add<S, Output=S> + Sub<S, Output=S> + Mul<S, Output=S> + MaybeDebug {
+    /// The free space that cannot hold a rectangle of size `min_size`, e.g. the smallest or a
+    /// typical rectangle still to be packed, as disjoint rectangles. Unlike the free rectangles,
+    /// which overlap, this is space that is truly wasted unless something smaller is packed.
+    ///
+    /// A free rectangle holds `min_size` if it fits its padded size, rotated if rotation is
+    /// allowed. Space within any free rectangle that holds it is not wasted.
+    pub fn waste(&self, min_size: &Size<S>) -> Vec<Rect<S>> {
+        let mut sizes = vec![self.padded(min_size)];
+        if self.config.allow_rotation {
+            sizes.push(self.padded(&Size::new(min_size.height.clone(), min_size.width.clone())));
+        }
+        let holds = |free: &Rect<S>| sizes.iter().any(|x| x.width <= free.width() && x.height <= free.height());
+
+        let (usable, small): (Vec<&Rect<S>>, Vec<&Rect<S>>) = self.empty.iter().partition(|x| holds(x));
+        let mut waste: Vec<Rect<S>> = Vec::new();
+        for free in small.into_iter() {
+            // Only the parts of the free rectangle outside the usable rectangles and the waste
+            // found so far are added, so that the waste is disjoint.
+            let mut parts = vec![free.clone()];
+            for covered in usable.iter().cloned().chain(waste.iter()) {
+                parts = parts.iter().flat_map(|x| difference(x, covered)).collect();
+            }
+            waste.extend(parts);
+        }
+
+        waste
+    }
+
+    /// The total area of the space returned by `waste`, or `None` if there is none
+    pub fn wasted_area(&self, min_size: &Size<S>) -> Option<S> {
+        sum(self.waste(min_size).iter().map(|x| x.area()))
+    }
+}
+
+/// The parts of `rect` outside `sub`, as up to four disjoint rectangles: the parts to the left
+/// and right of `sub` across the full height of `rect`, and the parts above and below it in
+/// between.
+pub fn difference<S>(rect: &Rect<S>, sub: &Rect<S>) -> Vec<Rect<S>> where S: Clone + PartialOrd {
+    let overlap = match rect.intersection(sub) {
+        Some(ref x) if x.min.x < x.max.x && x.min.y < x.max.y => x.clone(),
+        _ => return vec![rect.clone()],
+    };
+
+    let mut parts = Vec::new();
+    if rect.min.x < overlap.min.x {
+        parts.push(Rect::new(rect.min.clone(), Point::new(overlap.min.x.clone(), rect.max.y.clone())));
+    }
+    if overlap.max.x < rect.max.x {
+        parts.push(Rect::new(Point::new(overlap.max.x.clone(), rect.min.y.clone()), rect.max.clone()));
+    }
+    if rect.min.y < overlap.min.y {
+        parts.push(Rect::new(Point::new(overlap.min.x.clone(), rect.min.y.clone()), Point::new(overlap.max.x.clone(), overlap.min.y.clone())));
+    }
+    if overlap.max.y < rect.max.y {
+        parts.push(Rect::new(Point::new(overlap.min.x.clone(), overlap.max.y.clone()), Point::new(overlap.max.x.clone(), rect.max.y.clone())));
+    }
+    parts
+}
+
+#[cfg(test)]
+mod test {
+    use RectPacker;
+    use rect::Rect;
+    use size::Size;
+
+    #[test]
+    fn wasted_space() {
+        let mut packer = RectPacker::builder().bin(0,0,10,10).build();
+        packer.pack(8,8);
+        packer.pack(2,2);
+
+        // The free space is a 10x2 strip at the bottom and a 2x8 strip to the right
+        let waste = packer.waste(&Size::new(3,3));
+        assert_eq!(waste.iter().map(|x| x.area()).sum::<u32>(), 32);
+        for (i, a) in waste.iter().enumerate() {
+            assert!(waste[i + 1..].iter().all(|b| !a.intersects(b)));
+        }
+
+        assert_eq!(packer.waste(&Size::new(2,2)), vec![]);
+        assert_eq!(packer.wasted_area(&Size::new(9,2)), Some(12));
+        assert_eq!(packer.waste(&Size::new(9,2)), vec![Rect::new((8,2), (10,8))]);
+    }
+}